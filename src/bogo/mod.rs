@@ -8,29 +8,40 @@ use crate::game::{Game, GameArena};
 
 pub struct BogoArena<G: Game> {
     game: G,
+    best_game: G,
     best_score: f64,
-    rng: ThreadRng
+    rng: ThreadRng,
+    actions: Vec<G::Action>
 }
 
 impl<G: Game> BogoArena<G> {
     pub fn new(game: G) -> Self {
         Self {
+            best_game: game.start(),
             game,
             best_score: f64::NEG_INFINITY,
-            rng: rand::thread_rng()
+            rng: rand::thread_rng(),
+            actions: vec![]
         }
     }
+
+    pub fn best_game(&self) -> G {
+        self.best_game.clone()
+    }
 }
 
 impl<G: Game> GameArena<G> for BogoArena<G> {
     fn play_round(&mut self) {
         let mut g = self.game.clone();
         while !g.is_terminal() {
-            let act = *g.get_actions().choose(&mut self.rng).unwrap();
+            g.get_actions(&mut self.actions);
+            let act = *self.actions.choose(&mut self.rng).unwrap();
             g.act(act);
         }
-        if g.get_score() > self.best_score {
-            self.best_score = g.get_score();
+        let score = g.get_score();
+        if score > self.best_score {
+            self.best_score = score;
+            self.best_game = g;
         }
     }
 
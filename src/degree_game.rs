@@ -0,0 +1,273 @@
+use crate::game::{Game, GraphGame};
+use crate::graph::Graph;
+
+/*
+ * A two phase formulation of the spanning tree problem.
+ * First a non-increasing, near-regular degree sequence summing to 2m is chosen one vertex at a
+ * time, then it is realized edge by edge. Realization lays off one vertex at a time in the
+ * Havel-Hakimi order, and each edge choice is only offered if the residual sequence remains
+ * graphical, so every play ends in a graph with exactly m edges.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DegreeAction {
+    // Degree of the next vertex in the sequence
+    Degree(usize),
+    // Edge from the vertex being laid off to a neighbour
+    Edge(usize, usize),
+}
+
+#[derive(Clone, Debug)]
+pub struct DegreeGame<G: Graph> {
+    max_edges: usize,
+    num_vertices: usize,
+    // Largest allowed difference between nonzero degrees
+    spread: usize,
+    feasible: bool,
+    degrees: Vec<usize>,
+    degree_sum: usize,
+    residual: Vec<usize>,
+    // Vertex currently being laid off and the last neighbour chosen for it
+    current_vertex: Option<usize>,
+    last_neighbour: Option<usize>,
+    num_edges_added: usize,
+    graph: G,
+}
+
+impl<G: Graph> DegreeGame<G> {
+    pub fn new(m: usize, n: usize, spread: usize) -> Self {
+        let mut game = Self {
+            max_edges: m,
+            num_vertices: n,
+            spread,
+            feasible: true,
+            degrees: Vec::with_capacity(n),
+            degree_sum: 0,
+            residual: vec![0; n],
+            current_vertex: None,
+            last_neighbour: None,
+            num_edges_added: 0,
+            graph: G::empty(n),
+        };
+        let mut actions = vec![];
+        game.degree_actions(&mut actions);
+        game.feasible = m > 0 && !actions.is_empty();
+        game
+    }
+
+    fn choosing_degrees(&self) -> bool {
+        self.degree_sum < 2 * self.max_edges
+    }
+
+    fn highest_degree(&self) -> usize {
+        match self.degrees.last() {
+            Some(&d) => d,
+            None => self.num_vertices.saturating_sub(1),
+        }
+    }
+
+    fn degree_feasible(&self, k: usize) -> bool {
+        let index = self.degrees.len();
+        let remaining = 2 * self.max_edges - self.degree_sum;
+        if index >= self.num_vertices || k == 0 || k > self.highest_degree().min(remaining) {
+            return false;
+        }
+        let max_degree = *self.degrees.first().unwrap_or(&k);
+        let low = max_degree.saturating_sub(self.spread).max(1);
+        if index > 0 && k < low {
+            return false;
+        }
+        let mut prefix = self.degrees.clone();
+        prefix.push(k);
+        completable(&mut prefix, remaining - k, k, low, self.num_vertices - index - 1)
+    }
+
+    fn degree_actions(&self, actions: &mut Vec<DegreeAction>) {
+        actions.extend(
+            (1..=self.highest_degree())
+                .rev()
+                .filter(|&k| self.degree_feasible(k))
+                .map(DegreeAction::Degree),
+        );
+    }
+
+    /*
+     * Returns the vertex being laid off, which is the vertex of largest residual degree
+     * (lowest index on ties) once the previous one is exhausted
+     */
+    fn laid_off_vertex(&self) -> Option<usize> {
+        match self.current_vertex {
+            Some(v) if self.residual[v] > 0 => Some(v),
+            _ => {
+                let mut best = None;
+                for (i, &r) in self.residual.iter().enumerate() {
+                    if r > 0 && best.is_none_or(|b: usize| r > self.residual[b]) {
+                        best = Some(i);
+                    }
+                }
+                best
+            }
+        }
+    }
+
+    fn neighbour_actions(&self, actions: &mut Vec<DegreeAction>) {
+        let Some(v) = self.laid_off_vertex() else {
+            return;
+        };
+        actions.extend(
+            (self.first_neighbour(v)..self.num_vertices)
+                .filter(|&u| u != v && self.residual[u] > 0 && self.can_lay_off(v, u))
+                .map(|u| DegreeAction::Edge(v, u)),
+        );
+    }
+
+    /*
+     * Neighbours of a vertex are chosen in increasing order, a fresh vertex starts from scratch
+     */
+    fn first_neighbour(&self, v: usize) -> usize {
+        match (self.current_vertex, self.last_neighbour) {
+            (Some(c), Some(l)) if c == v => l + 1,
+            _ => 0,
+        }
+    }
+
+    /*
+     * Checks whether joining v to u still admits a realization in which the remaining neighbours
+     * of v have larger indices than u. By the usual Havel-Hakimi exchange argument it suffices to
+     * join v to the candidates of largest residual degree and test the result for graphicality.
+     */
+    fn can_lay_off(&self, v: usize, u: usize) -> bool {
+        let mut residual = self.residual.clone();
+        residual[u] -= 1;
+        let needed = residual[v] - 1;
+        residual[v] = 0;
+
+        let mut candidates: Vec<usize> = (u + 1..self.num_vertices)
+            .filter(|&w| w != v && residual[w] > 0)
+            .collect();
+        if candidates.len() < needed {
+            return false;
+        }
+        candidates.sort_by(|&a, &b| residual[b].cmp(&residual[a]));
+        for &w in &candidates[..needed] {
+            residual[w] -= 1;
+        }
+        is_graphical(&residual)
+    }
+}
+
+impl<G: Graph> Game for DegreeGame<G> {
+    type Action = DegreeAction;
+
+    fn get_actions(&self, actions: &mut Vec<DegreeAction>) {
+        actions.clear();
+        if self.is_terminal() {
+            return;
+        }
+        if self.choosing_degrees() {
+            self.degree_actions(actions)
+        } else {
+            self.neighbour_actions(actions)
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        !self.feasible || self.num_edges_added >= self.max_edges
+    }
+
+    fn act(&mut self, action: DegreeAction) -> bool {
+        if self.is_terminal() {
+            return false;
+        }
+        match action {
+            DegreeAction::Degree(k) if self.choosing_degrees() => {
+                if !self.degree_feasible(k) {
+                    return false;
+                }
+                self.residual[self.degrees.len()] = k;
+                self.degrees.push(k);
+                self.degree_sum += k;
+            }
+            DegreeAction::Edge(v, u) if !self.choosing_degrees() => {
+                if Some(v) != self.laid_off_vertex()
+                    || u < self.first_neighbour(v)
+                    || u >= self.num_vertices
+                    || u == v
+                    || self.residual[u] == 0
+                    || !self.can_lay_off(v, u)
+                {
+                    return false;
+                }
+                self.graph.add_edge(v, u);
+                self.residual[v] -= 1;
+                self.residual[u] -= 1;
+                self.current_vertex = Some(v);
+                self.last_neighbour = Some(u);
+                self.num_edges_added += 1;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn get_score(&self) -> f64 {
+        self.graph.weighted_spanning_tree_count().round()
+    }
+
+    fn start(&self) -> Self {
+        Self::new(self.max_edges, self.num_vertices, self.spread)
+    }
+
+    fn score_key(&self) -> Option<Vec<usize>> {
+        Some(self.graph.canonical_form())
+    }
+}
+
+impl<G: Graph> GraphGame for DegreeGame<G> {
+    type Graph = G;
+
+    fn graph(&self) -> &G {
+        &self.graph
+    }
+}
+
+/*
+ * Erdős–Gallai test, zero entries are ignored
+ */
+fn is_graphical(degrees: &[usize]) -> bool {
+    let mut d: Vec<usize> = degrees.iter().copied().filter(|&x| x > 0).collect();
+    if d.iter().sum::<usize>() % 2 != 0 {
+        return false;
+    }
+    d.sort_unstable_by(|a, b| b.cmp(a));
+    let mut lhs = 0;
+    for k in 1..=d.len() {
+        lhs += d[k - 1];
+        let rhs = k * (k - 1) + d[k..].iter().map(|&x| x.min(k)).sum::<usize>();
+        if lhs > rhs {
+            return false;
+        }
+    }
+    true
+}
+
+/*
+ * Whether the non-increasing prefix can be extended by at most `slots` degrees in [low, high]
+ * summing to `remaining` such that the whole sequence is graphical
+ */
+fn completable(prefix: &mut Vec<usize>, remaining: usize, high: usize, low: usize, slots: usize) -> bool {
+    if remaining == 0 {
+        return is_graphical(prefix);
+    }
+    if slots == 0 || remaining > slots * high {
+        return false;
+    }
+    for k in (low..=high.min(remaining)).rev() {
+        prefix.push(k);
+        let found = completable(prefix, remaining - k, k, low, slots - 1);
+        prefix.pop();
+        if found {
+            return true;
+        }
+    }
+    false
+}
@@ -0,0 +1,79 @@
+/*
+ * Exhaustive search over the connected graphs with a given number of edges, as ground truth for
+ * MCTS on small edge counts.
+ * Graphs are grown one edge at a time, either between two vertices already in use or to a new
+ * vertex, and deduplicated by canonical form at every size so that each isomorphism class is kept
+ * once. Every connected graph is reached, since removing an edge on a cycle or a pendant edge
+ * leaves a connected graph with one edge less.
+ */
+
+use std::collections::HashMap;
+use crate::graph::Graph;
+
+#[derive(Debug, Clone)]
+pub struct Exhaustive<G: Graph> {
+    pub edge_count: usize,
+    // Number of non-isomorphic connected graphs enumerated with edge_count edges
+    pub graphs: usize,
+    pub spanning_trees: usize,
+    // One graph per isomorphism class attaining spanning_trees
+    pub maximizers: Vec<G>,
+}
+
+/*
+ * Enumerates the connected graphs with m edges on at most n vertices, m + 1 vertices being
+ * enough for all of them
+ */
+pub fn exhaustive<G: Graph>(m: usize, n: usize) -> Exhaustive<G> {
+    // The lone vertex, which counts no spanning trees since Graph ignores isolated vertices
+    if m == 0 && n > 0 {
+        let g = G::empty(n);
+        return Exhaustive {
+            edge_count: 0,
+            graphs: 1,
+            spanning_trees: g.spanning_tree_count(),
+            maximizers: vec![g],
+        };
+    }
+    let mut level: HashMap<Vec<usize>, G> = HashMap::new();
+    if m > 0 && n > 1 {
+        let mut g = G::empty(n);
+        g.add_edge(1, 0);
+        level.insert(g.canonical_form(), g);
+    }
+    for _k in 1..m {
+        let mut next = HashMap::new();
+        for g in level.values() {
+            let used = g.lowest_free_vertex().unwrap_or(n);
+            for i in 1..=used.min(n - 1) {
+                for j in 0..i {
+                    if g.weight(i, j) != 0.0 {
+                        continue;
+                    }
+                    let mut h = g.clone();
+                    h.add_edge(i, j);
+                    next.entry(h.canonical_form()).or_insert(h);
+                }
+            }
+        }
+        level = next;
+    }
+
+    let graphs: Vec<G> = level.into_values().collect();
+    let counts = G::batch_spanning_tree_counts(&graphs.iter().collect::<Vec<_>>());
+    let counts: Vec<usize> = counts.into_iter().map(|c| c.round() as usize).collect();
+    let spanning_trees = counts.iter().copied().max().unwrap_or(0);
+    let mut maximizers: Vec<G> = graphs
+        .iter()
+        .zip(&counts)
+        .filter(|&(_, &c)| c == spanning_trees)
+        .map(|(g, _)| g.clone())
+        .collect();
+    maximizers.sort_by_key(|g| g.edges());
+    Exhaustive {
+        edge_count: m,
+        graphs: graphs.len(),
+        spanning_trees,
+        maximizers,
+    }
+}
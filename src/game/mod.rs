@@ -1,12 +1,38 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+use crate::graph::Graph;
+
 pub trait Game: Clone {
-    fn get_actions(&self) -> Vec<u64>;
+    type Action: Copy + Eq + Hash + Debug;
+
+    // Clears `actions` and fills it with the actions available in this state
+    fn get_actions(&self, actions: &mut Vec<Self::Action>);
     fn is_terminal(&self) -> bool;
-    fn act(&mut self, action: u64) -> bool;
+    fn act(&mut self, action: Self::Action) -> bool;
     fn get_score(&self) -> f64;
     fn start(&self) -> Self;
+
+    // Equal for states known to score the same, such as isomorphic graphs, to memoize get_score
+    fn score_key(&self) -> Option<Vec<usize>> {
+        None
+    }
+
+    // Scores of many terminal games at once, for games whose scoring can share work
+    fn score_batch(games: &[&Self]) -> Vec<f64>
+    where
+        Self: Sized,
+    {
+        games.iter().map(|g| g.get_score()).collect()
+    }
+}
+
+// Games whose states are graphs
+pub trait GraphGame: Game {
+    type Graph: Graph;
+    fn graph(&self) -> &Self::Graph;
 }
 
 pub trait GameArena<G: Game> {
     fn play_round(&mut self);
     fn best(&self) -> f64;
-}
\ No newline at end of file
+}
@@ -0,0 +1,269 @@
+use std::collections::VecDeque;
+use std::fmt;
+use nalgebra::DMatrix;
+use crate::graph::symmetry::Symmetry;
+use crate::graph::Graph;
+
+/*
+ * Structural properties of a graph, as usually checked for a maximizer after a search.
+ * Isolated vertices are ignored throughout, so a graph is connected if its edges form one
+ * component.
+ */
+#[derive(Debug, Clone)]
+pub struct Analysis {
+    // Non-increasing
+    pub degree_sequence: Vec<usize>,
+    // The common degree if every vertex has the same degree
+    pub regularity: Option<usize>,
+    // None if disconnected
+    pub diameter: Option<usize>,
+    // None if acyclic
+    pub girth: Option<usize>,
+    pub vertex_connectivity: usize,
+    pub edge_connectivity: usize,
+    // Non-decreasing
+    pub laplacian_spectrum: Vec<f64>,
+    pub algebraic_connectivity: f64,
+    pub automorphisms: u128,
+}
+
+pub fn analyze<G: Graph>(g: &G) -> Analysis {
+    let adjacency = compact_adjacency(g);
+    let n = adjacency.len();
+    // Nothing to decompose, and no distances to take the diameter over
+    if n == 0 {
+        return Analysis {
+            degree_sequence: vec![],
+            regularity: None,
+            diameter: None,
+            girth: None,
+            vertex_connectivity: 0,
+            edge_connectivity: 0,
+            laplacian_spectrum: vec![],
+            algebraic_connectivity: 0.0,
+            automorphisms: 1,
+        };
+    }
+
+    let mut degree_sequence: Vec<usize> = adjacency.iter().map(|a| a.len()).collect();
+    degree_sequence.sort_unstable_by(|a, b| b.cmp(a));
+    let regularity = match (degree_sequence.first(), degree_sequence.last()) {
+        (Some(&max), Some(&min)) if max == min => Some(max),
+        _ => None,
+    };
+
+    let distances: Vec<Vec<Option<usize>>> = (0..n).map(|v| bfs(&adjacency, v)).collect();
+    let diameter = distances
+        .iter()
+        .flatten()
+        .try_fold(0, |d, &x| x.map(|x| d.max(x)));
+
+    let mut laplacian = DMatrix::<f64>::zeros(n, n);
+    for (v, neighbours) in adjacency.iter().enumerate() {
+        laplacian[(v, v)] = neighbours.len() as f64;
+        for &u in neighbours {
+            laplacian[(v, u)] -= 1.0;
+        }
+    }
+    let mut laplacian_spectrum: Vec<f64> = laplacian.symmetric_eigenvalues().iter().copied().collect();
+    laplacian_spectrum.sort_by(|a, b| a.total_cmp(b));
+    let algebraic_connectivity = laplacian_spectrum.get(1).copied().unwrap_or(0.0).max(0.0);
+
+    Analysis {
+        degree_sequence,
+        regularity,
+        diameter,
+        girth: girth(&adjacency),
+        vertex_connectivity: if diameter.is_some() { vertex_connectivity(&adjacency) } else { 0 },
+        edge_connectivity: if diameter.is_some() { edge_connectivity(&adjacency) } else { 0 },
+        laplacian_spectrum,
+        algebraic_connectivity,
+        automorphisms: Symmetry::new(&adjacency).automorphism_count(),
+    }
+}
+
+/*
+ * Whether all weights are integral, so that the graph is a multigraph. Other weighted graphs are
+ * taken with every edge once, however light, rather than rounding weights to multiplicities.
+ */
+pub(crate) fn is_multigraph<G: Graph>(g: &G, edges: &[(usize, usize)]) -> bool {
+    edges.iter().all(|&(i, j)| g.weight(i, j).fract() == 0.0)
+}
+
+/*
+ * Adjacency lists of the non-isolated vertices, relabelled to 0..order in increasing order.
+ * A neighbour appears once for each copy of a multi edge, see is_multigraph.
+ */
+pub(crate) fn compact_adjacency<G: Graph>(g: &G) -> Vec<Vec<usize>> {
+    let edges = g.edges();
+    let mut vertices: Vec<usize> = edges.iter().flat_map(|&(i, j)| [i, j]).collect();
+    vertices.sort_unstable();
+    vertices.dedup();
+    let multigraph = is_multigraph(g, &edges);
+    let mut adjacency = vec![vec![]; vertices.len()];
+    for (i, j) in edges {
+        let a = vertices.binary_search(&i).unwrap();
+        let b = vertices.binary_search(&j).unwrap();
+        for _k in 0..if multigraph { g.multiplicity(i, j) } else { 1 } {
+            adjacency[a].push(b);
+            adjacency[b].push(a);
+        }
+    }
+    adjacency
+}
+
+fn bfs(adjacency: &[Vec<usize>], source: usize) -> Vec<Option<usize>> {
+    let mut distance = vec![None; adjacency.len()];
+    distance[source] = Some(0);
+    let mut queue = VecDeque::from([source]);
+    while let Some(v) = queue.pop_front() {
+        let d = distance[v].unwrap();
+        for &u in &adjacency[v] {
+            if distance[u].is_none() {
+                distance[u] = Some(d + 1);
+                queue.push_back(u);
+            }
+        }
+    }
+    distance
+}
+
+/*
+ * Shortest cycle through a non-tree edge of a BFS tree, minimized over all roots.
+ * Multi edges are cycles of length 2.
+ */
+fn girth(adjacency: &[Vec<usize>]) -> Option<usize> {
+    let parallel = adjacency.iter().any(|neighbours| {
+        let mut distinct = neighbours.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        distinct.len() < neighbours.len()
+    });
+    if parallel {
+        return Some(2);
+    }
+    let mut best: Option<usize> = None;
+    for source in 0..adjacency.len() {
+        let mut distance = vec![usize::MAX; adjacency.len()];
+        let mut parent = vec![usize::MAX; adjacency.len()];
+        distance[source] = 0;
+        let mut queue = VecDeque::from([source]);
+        while let Some(v) = queue.pop_front() {
+            for &u in &adjacency[v] {
+                if distance[u] == usize::MAX {
+                    distance[u] = distance[v] + 1;
+                    parent[u] = v;
+                    queue.push_back(u);
+                } else if parent[v] != u {
+                    let cycle = distance[u] + distance[v] + 1;
+                    best = Some(best.map_or(cycle, |b| b.min(cycle)));
+                }
+            }
+        }
+    }
+    best
+}
+
+/*
+ * Maximum flow by shortest augmenting paths over a dense capacity matrix
+ */
+fn max_flow(capacity: &mut [Vec<usize>], source: usize, sink: usize, limit: usize) -> usize {
+    let n = capacity.len();
+    let mut flow = 0;
+    while flow < limit {
+        let mut parent = vec![usize::MAX; n];
+        parent[source] = source;
+        let mut queue = VecDeque::from([source]);
+        while let Some(v) = queue.pop_front() {
+            if v == sink {
+                break;
+            }
+            for u in 0..n {
+                if parent[u] == usize::MAX && capacity[v][u] > 0 {
+                    parent[u] = v;
+                    queue.push_back(u);
+                }
+            }
+        }
+        if parent[sink] == usize::MAX {
+            break;
+        }
+        let mut v = sink;
+        while v != source {
+            capacity[parent[v]][v] -= 1;
+            capacity[v][parent[v]] += 1;
+            v = parent[v];
+        }
+        flow += 1;
+    }
+    flow
+}
+
+// Minimum number of edges whose removal disconnects the graph, the graph being connected
+fn edge_connectivity(adjacency: &[Vec<usize>]) -> usize {
+    let n = adjacency.len();
+    let mut best = adjacency.iter().map(|a| a.len()).min().unwrap_or(0);
+    for sink in 1..n {
+        let mut capacity = vec![vec![0; n]; n];
+        for (v, neighbours) in adjacency.iter().enumerate() {
+            for &u in neighbours {
+                capacity[v][u] += 1;
+            }
+        }
+        best = best.min(max_flow(&mut capacity, 0, sink, best));
+    }
+    best
+}
+
+/*
+ * Minimum number of vertices whose removal disconnects the graph, or n - 1 for complete graphs.
+ * By Even's argument a minimum separator misses one of any connectivity + 1 vertices, so only
+ * sources among the first few vertices are tried. Each vertex is split into an in and an out
+ * copy joined by a unit capacity arc.
+ */
+fn vertex_connectivity(adjacency: &[Vec<usize>]) -> usize {
+    let n = adjacency.len();
+    let mut best = n.saturating_sub(1);
+    let mut source = 0;
+    while source < n && source <= best {
+        for sink in 0..n {
+            if sink == source || adjacency[source].contains(&sink) {
+                continue;
+            }
+            let mut capacity = vec![vec![0; 2 * n]; 2 * n];
+            for (v, neighbours) in adjacency.iter().enumerate() {
+                capacity[2 * v][2 * v + 1] = 1;
+                for &u in neighbours {
+                    capacity[2 * v + 1][2 * u] = n;
+                }
+            }
+            best = best.min(max_flow(&mut capacity, 2 * source + 1, 2 * sink, best));
+        }
+        source += 1;
+    }
+    best
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Degree Sequence {:?}", self.degree_sequence)?;
+        match self.regularity {
+            Some(d) => writeln!(f, "Regular of degree {}", d)?,
+            None => writeln!(f, "Not regular")?,
+        }
+        match self.diameter {
+            Some(d) => writeln!(f, "Diameter {}", d)?,
+            None => writeln!(f, "Diameter infinite (disconnected)")?,
+        }
+        match self.girth {
+            Some(g) => writeln!(f, "Girth {}", g)?,
+            None => writeln!(f, "Girth infinite (acyclic)")?,
+        }
+        writeln!(f, "Vertex Connectivity {}", self.vertex_connectivity)?;
+        writeln!(f, "Edge Connectivity {}", self.edge_connectivity)?;
+        let spectrum: Vec<String> = self.laplacian_spectrum.iter().map(|&x| format!("{:.4}", if x > 0.0 { x } else { 0.0 })).collect();
+        writeln!(f, "Laplacian Spectrum [{}]", spectrum.join(", "))?;
+        writeln!(f, "Algebraic Connectivity {:.4}", self.algebraic_connectivity)?;
+        write!(f, "Automorphisms {}", self.automorphisms)
+    }
+}
@@ -1,14 +1,82 @@
-use crate::graph::Graph;
+use crate::graph::{deduplicate, Graph};
 use nalgebra::{DMatrix, Dyn, OMatrix};
 
-const EPSILON: f64 = 1e-6;
-
 #[derive(Debug, Clone)]
 pub struct LapGraph {
     laplacian: OMatrix<f64, Dyn, Dyn>,
     vertex_count: usize,
 }
 
+impl LapGraph {
+    /*
+     * Diagonal of the Cholesky factor of the Laplacian restricted to the non-isolated vertices with
+     * the last of them removed, whose squared product is the spanning tree count.
+     * None if the graph is disconnected, which is decided before any numeric work. For connected
+     * graphs the reduced Laplacian is positive definite, so the factorization exists.
+     */
+    fn reduced_laplacian_cholesky_diagonal(&self) -> Option<Vec<f64>> {
+        if !self.is_connected() {
+            return None;
+        }
+        Some(self.connected_cholesky_diagonal())
+    }
+
+    // As above for a graph already known to be connected
+    fn connected_cholesky_diagonal(&self) -> Vec<f64> {
+        let mut vertices: Vec<usize> = (0..self.vertex_count)
+            .filter(|&v| self.laplacian[(v, v)] != 0.0)
+            .collect();
+        vertices.pop();
+        let reduced = self.laplacian.select_rows(&vertices).select_columns(&vertices);
+        let cholesky = reduced.cholesky().expect("reduced Laplacian of a connected graph is positive definite");
+        cholesky.l_dirty().diagonal().iter().copied().collect()
+    }
+
+    /*
+     * Weighted spanning tree counts of distinct connected graphs on the same vertices.
+     * Vertices whose Laplacian rows agree in every graph, such as those whose pairs were all decided
+     * before the rollouts diverged, are eliminated once: with S those vertices and T the others,
+     * det(L) = det(L[S, S]) det(L[T, T] - L[T, S] L[S, S]^-1 L[S, T]), where only L[T, T] differs
+     * between the graphs. None if few vertices are shared.
+     */
+    fn shared_spanning_tree_counts(graphs: &[&LapGraph]) -> Option<Vec<f64>> {
+        let first = graphs[0];
+        let shared: Vec<usize> = (0..first.vertex_count)
+            .filter(|&v| first.laplacian[(v, v)] != 0.0)
+            .filter(|&v| graphs[1..].iter().all(|g| g.laplacian.column(v) == first.laplacian.column(v)))
+            .collect();
+        // Too few shared vertices do not pay for the extra copies
+        if 4 * shared.len() < first.order() {
+            return None;
+        }
+        let rest: Vec<usize> = (0..first.vertex_count).filter(|v| !shared.contains(v)).collect();
+
+        let block = first.laplacian.select_rows(&shared);
+        let cholesky = block.select_columns(&shared).cholesky()?;
+        let coupling = block.select_columns(&rest);
+        let correction = coupling.transpose() * cholesky.solve(&coupling);
+        let shared_count: f64 = cholesky.l_dirty().diagonal().iter().map(|x| x * x).product();
+
+        graphs
+            .iter()
+            .map(|g| {
+                // Positions in rest of the non-isolated vertices, the last of them being removed
+                let mut remaining: Vec<usize> = (0..rest.len())
+                    .filter(|&k| g.laplacian[(rest[k], rest[k])] != 0.0)
+                    .collect();
+                remaining.pop()?;
+                let schur = DMatrix::from_fn(remaining.len(), remaining.len(), |a, b| {
+                    let (a, b) = (remaining[a], remaining[b]);
+                    g.laplacian[(rest[a], rest[b])] - correction[(a, b)]
+                });
+                let cholesky = schur.cholesky()?;
+                let count: f64 = cholesky.l_dirty().diagonal().iter().map(|x| x * x).product();
+                Some(shared_count * count)
+            })
+            .collect()
+    }
+}
+
 impl Graph for LapGraph {
     fn empty(vertex_count: usize) -> Self {
         Self {
@@ -39,13 +107,14 @@ impl Graph for LapGraph {
 
         debug_assert!(
             self.laplacian[(i, j)] == 0.0,
-            "LapGraph::add_edge does not support multi edges"
+            "LapGraph::add_edge does not support multi edges, see LapGraph::add_parallel_edge"
         );
 
-        self.laplacian[(i, j)] -= 1.0;
-        self.laplacian[(j, i)] -= 1.0;
-        self.laplacian[(i, i)] += 1.0;
-        self.laplacian[(j, j)] += 1.0;
+        self.add_parallel_edge(i, j);
+    }
+
+    fn add_parallel_edge(&mut self, i: usize, j: usize) {
+        self.add_weighted_edge(i, j, 1.0);
     }
 
     fn order(&self) -> usize {
@@ -62,25 +131,47 @@ impl Graph for LapGraph {
     }
 
     fn spanning_tree_count(&self) -> usize {
-        let eigen = self.laplacian.symmetric_eigenvalues();
-        let mut p = 1.0;
-        let mut c = 0;
-        for i in 0..eigen.len() {
-            if eigen[i].abs() > EPSILON {
-                p *= eigen[i];
-            } else {
-                c += 1;
-            }
-        }
+        self.weighted_spanning_tree_count().round() as usize
+    }
 
-        if c > self.vertex_count - self.order() + 1 {
-            0
-        } else {
-            if self.order() == 0 {
-                return 0;
-            }
-            (p / self.order() as f64).round() as usize
+    fn weighted_spanning_tree_count(&self) -> f64 {
+        self.reduced_laplacian_cholesky_diagonal()
+            .map_or(0.0, |d| d.iter().map(|x| x * x).product())
+    }
+
+    fn ln_spanning_tree_count(&self) -> f64 {
+        self.reduced_laplacian_cholesky_diagonal()
+            .map_or(f64::NEG_INFINITY, |d| d.iter().map(|x| 2.0 * x.ln()).sum())
+    }
+
+    fn batch_spanning_tree_counts(graphs: &[&Self]) -> Vec<f64> {
+        // Graphs with equal degrees are compared in full
+        let fingerprint = |g: &&LapGraph| {
+            g.laplacian
+                .diagonal()
+                .iter()
+                .fold(0u64, |h, d| h.rotate_left(7) ^ d.to_bits())
+        };
+        let (representatives, class) = deduplicate(graphs, fingerprint, |a, b| a.laplacian == b.laplacian);
+        let mut counts = vec![0.0; representatives.len()];
+        let connected: Vec<usize> = (0..representatives.len())
+            .filter(|&r| graphs[representatives[r]].is_connected())
+            .collect();
+        let distinct: Vec<&LapGraph> = connected.iter().map(|&r| graphs[representatives[r]]).collect();
+        let shared = match distinct.len() {
+            0 | 1 => None,
+            _ => Self::shared_spanning_tree_counts(&distinct),
+        };
+        let connected_counts = shared.unwrap_or_else(|| {
+            distinct
+                .iter()
+                .map(|g| g.connected_cholesky_diagonal().iter().map(|x| x * x).product())
+                .collect()
+        });
+        for (&r, count) in connected.iter().zip(connected_counts) {
+            counts[r] = count;
         }
+        class.iter().map(|&c| counts[c]).collect()
     }
 
     fn degree(&self, vertex: usize) -> usize {
@@ -99,20 +190,34 @@ impl Graph for LapGraph {
         None
     }
 
-    fn print_edges(&self) {
-        let mut s = self.size();
-        print!("[");
+    fn add_weighted_edge(&mut self, i: usize, j: usize, weight: f64) {
+        debug_assert!(i != j, "LapGraph::add_weighted_edge does not support self loops");
+        debug_assert!(weight > 0.0, "LapGraph::add_weighted_edge weights must be positive");
+
+        self.laplacian[(i, j)] -= weight;
+        self.laplacian[(j, i)] -= weight;
+        self.laplacian[(i, i)] += weight;
+        self.laplacian[(j, j)] += weight;
+    }
+
+    fn weight(&self, i: usize, j: usize) -> f64 {
+        -self.laplacian[(i, j)]
+    }
+
+    // Weights of multigraphs are integral, other weights are rounded
+    fn multiplicity(&self, i: usize, j: usize) -> usize {
+        (-self.laplacian[(i, j)]).round() as usize
+    }
+
+    fn edges(&self) -> Vec<(usize, usize)> {
+        let mut edges = Vec::with_capacity(self.size());
         for i in 1..self.vertex_count {
             for j in 0..i {
-                if self.laplacian[(i, j)] == -1.0 {
-                    print!("({i}, {j})");
-                    s -= 1;
-                    if s > 0 {
-                        print!(", ");
-                    }
+                if self.laplacian[(i, j)] < 0.0 {
+                    edges.push((i, j));
                 }
             }
         }
-        println!("]")
+        edges
     }
 }
@@ -1,14 +1,154 @@
+pub mod analysis;
 pub mod laplacian;
+pub mod render;
+pub mod sparse;
+pub(crate) mod symmetry;
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use crate::graph::analysis::compact_adjacency;
+use crate::graph::symmetry::Symmetry;
 
 // Implementation note: isolated vertices are not real and cannot hurt you
 pub trait Graph: Clone {
     fn empty(vertex_count: usize) -> Self;
     fn complete(vertex_count: usize) -> Self;
     fn add_edge(&mut self, i: usize, j: usize);
+    // Adds another copy of the edge (i, j), which may already be present
+    fn add_parallel_edge(&mut self, i: usize, j: usize);
+    // Number of copies of the edge (i, j) in a multigraph, 0 or 1 for simple graphs
+    fn multiplicity(&self, i: usize, j: usize) -> usize;
+    // Adds weight to the edge (i, j), creating it if absent. Unweighted edges have weight 1
+    fn add_weighted_edge(&mut self, i: usize, j: usize, weight: f64);
+    fn weight(&self, i: usize, j: usize) -> f64;
     fn order(&self) -> usize;
     fn size(&self) -> usize;
+    // Rounded weighted count, saturating at usize::MAX from about 20 vertices on in dense graphs
     fn spanning_tree_count(&self) -> usize;
+    // Sum over spanning trees of the product of their edge weights, by the weighted matrix-tree theorem
+    fn weighted_spanning_tree_count(&self) -> f64;
+    // Natural logarithm of the weighted spanning tree count, negative infinity if there are none.
+    // Stays finite where the count itself overflows
+    fn ln_spanning_tree_count(&self) -> f64;
+
+    /*
+     * Weighted spanning tree counts of many graphs at once, as for the rollouts of one expansion.
+     * Graphs with identical weighted edge sets are only counted once, and implementations may
+     * share further work between graphs which agree on part of their edges.
+     */
+    fn batch_spanning_tree_counts(graphs: &[&Self]) -> Vec<f64>
+    where
+        Self: Sized,
+    {
+        let edge_sets: Vec<Vec<(usize, usize, u64)>> = graphs
+            .iter()
+            .map(|g| {
+                g.edges()
+                    .into_iter()
+                    .map(|(i, j)| (i, j, g.weight(i, j).to_bits()))
+                    .collect()
+            })
+            .collect();
+        let fingerprint = |edges: &Vec<(usize, usize, u64)>| {
+            let mut hasher = DefaultHasher::new();
+            edges.hash(&mut hasher);
+            hasher.finish()
+        };
+        let (representatives, class) = deduplicate(&edge_sets, fingerprint, |a, b| a == b);
+        let counts: Vec<f64> = representatives
+            .iter()
+            .map(|&r| graphs[r].weighted_spanning_tree_count())
+            .collect();
+        class.iter().map(|&c| counts[c]).collect()
+    }
+
     fn degree(&self, vertex: usize) -> usize;
     fn lowest_free_vertex(&self) -> Option<usize>;
-    fn print_edges(&self);
+
+    /*
+     * Whether the non-isolated vertices form a single component, false if there are none.
+     * Decided by union-find over the edges, so spanning tree counts need no numeric zero test.
+     */
+    fn is_connected(&self) -> bool {
+        let edges = self.edges();
+        let n = edges.iter().map(|&(i, _)| i + 1).max().unwrap_or(0);
+        let mut parent: Vec<usize> = (0..n).collect();
+        fn root(parent: &mut [usize], mut v: usize) -> usize {
+            while parent[v] != v {
+                parent[v] = parent[parent[v]];
+                v = parent[v];
+            }
+            v
+        }
+        let mut components = self.order();
+        for (i, j) in edges {
+            let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+            if a != b {
+                parent[a] = b;
+                components -= 1;
+            }
+        }
+        components == 1
+    }
+
+    // Edges (i, j) with i > j in lexicographic order, listed once whatever their multiplicity
+    fn edges(&self) -> Vec<(usize, usize)>;
+    /*
+     * Equal for two graphs exactly when they are isomorphic, ignoring isolated vertices.
+     * Multiplicities are taken into account but other weights are not.
+     */
+    fn canonical_form(&self) -> Vec<usize> {
+        Symmetry::new(&compact_adjacency(self)).canonical_form()
+    }
+
+    // Multi edges are printed as (i, j)xk and other non-unit weights as (i, j):w
+    fn print_edges(&self) {
+        let edges: Vec<String> = self
+            .edges()
+            .into_iter()
+            .map(|(i, j)| {
+                let w = self.weight(i, j);
+                if w == 1.0 {
+                    format!("({i}, {j})")
+                } else if w.fract() == 0.0 {
+                    format!("({i}, {j})x{w}")
+                } else {
+                    format!("({i}, {j}):{w}")
+                }
+            })
+            .collect();
+        println!("[{}]", edges.join(", "))
+    }
+}
+
+/*
+ * Groups equal items, compared only when their fingerprints agree. Returns the index of the first
+ * item of each group, and for every item the position of its group in that list.
+ */
+pub(crate) fn deduplicate<T>(
+    items: &[T],
+    fingerprint: impl Fn(&T) -> u64,
+    same: impl Fn(&T, &T) -> bool,
+) -> (Vec<usize>, Vec<usize>) {
+    let fingerprints: Vec<u64> = items.iter().map(fingerprint).collect();
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    order.sort_unstable_by_key(|&k| (fingerprints[k], k));
+
+    let mut representatives: Vec<usize> = vec![];
+    let mut class = vec![0; items.len()];
+    // Groups of the current fingerprint are representatives[first..]
+    let mut first = 0;
+    for (position, &k) in order.iter().enumerate() {
+        if position > 0 && fingerprints[order[position - 1]] != fingerprints[k] {
+            first = representatives.len();
+        }
+        class[k] = match (first..representatives.len()).find(|&g| same(&items[representatives[g]], &items[k])) {
+            Some(g) => g,
+            None => {
+                representatives.push(k);
+                representatives.len() - 1
+            }
+        };
+    }
+    (representatives, class)
 }
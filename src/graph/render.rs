@@ -0,0 +1,214 @@
+use std::f64::consts::PI;
+use std::fmt::Write;
+use crate::graph::analysis::is_multigraph;
+use crate::graph::Graph;
+
+/*
+ * Rendering of graphs to Graphviz DOT and to self-contained SVG.
+ * Only vertices with an edge are drawn, and vertices are coloured from blue at the lowest degree
+ * to red at the highest, weighted degrees being used for weighted graphs. Multi edges are drawn
+ * as parallel edges in DOT, other weights as edge labels, and SVG lines are as thick as their
+ * weight relative to the lightest edge.
+ */
+
+const SVG_SIZE: f64 = 400.0;
+const SVG_MARGIN: f64 = 30.0;
+const VERTEX_RADIUS: f64 = 12.0;
+const SPRING_ITERATIONS: usize = 300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    // Vertices evenly spaced on a circle in label order
+    Circular,
+    // Fruchterman-Reingold force directed layout started from the circular one
+    Spring,
+}
+
+// Non-isolated vertices in increasing order
+fn vertices<G: Graph>(g: &G, edges: &[(usize, usize)]) -> Vec<usize> {
+    let mut v: Vec<usize> = edges.iter().flat_map(|&(i, j)| [i, j]).collect();
+    v.sort_unstable();
+    v.dedup();
+    debug_assert_eq!(v.len(), g.order());
+    v
+}
+
+// Sum of the weights at each vertex, aligned with vertices
+fn weighted_degrees<G: Graph>(g: &G, vertices: &[usize], edges: &[(usize, usize)]) -> Vec<f64> {
+    let mut degrees = vec![0.0; vertices.len()];
+    for &(i, j) in edges {
+        let w = g.weight(i, j);
+        degrees[vertices.binary_search(&i).unwrap()] += w;
+        degrees[vertices.binary_search(&j).unwrap()] += w;
+    }
+    degrees
+}
+
+fn degree_colour(degree: f64, degrees: &[f64]) -> String {
+    let min = degrees.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = degrees.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let t = if max > min {
+        (degree - min) / (max - min)
+    } else {
+        0.5
+    };
+    hue_to_rgb(240.0 * (1.0 - t))
+}
+
+// Fully saturated colour of the given hue in degrees, lightened so black labels stay readable
+fn hue_to_rgb(hue: f64) -> String {
+    let h = hue / 60.0;
+    let x = 1.0 - (h % 2.0 - 1.0).abs();
+    let (r, g, b) = match h as usize {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+    let light = |c: f64| (255.0 * (0.45 + 0.55 * c)).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", light(r), light(g), light(b))
+}
+
+pub fn to_dot<G: Graph>(g: &G) -> String {
+    let edges = g.edges();
+    let vertices = vertices(g, &edges);
+    let degrees = weighted_degrees(g, &vertices, &edges);
+    let mut out = String::from("graph G {\n    node [shape=circle, style=filled];\n");
+    for (&v, &d) in vertices.iter().zip(&degrees) {
+        writeln!(out, "    {} [fillcolor=\"{}\"];", v, degree_colour(d, &degrees)).unwrap();
+    }
+    let multigraph = is_multigraph(g, &edges);
+    for (i, j) in edges {
+        if multigraph {
+            for _k in 0..g.multiplicity(i, j) {
+                writeln!(out, "    {} -- {};", j, i).unwrap();
+            }
+        } else {
+            writeln!(out, "    {} -- {} [label=\"{:.4}\"];", j, i, g.weight(i, j)).unwrap();
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+pub fn to_svg<G: Graph>(g: &G, layout: Layout) -> String {
+    let edges = g.edges();
+    let vertices = vertices(g, &edges);
+    let positions = match layout {
+        Layout::Circular => circular_layout(vertices.len()),
+        Layout::Spring => spring_layout(&vertices, &edges),
+    };
+    let scale = |(x, y): (f64, f64)| {
+        let inner = SVG_SIZE - 2.0 * SVG_MARGIN;
+        (SVG_MARGIN + inner * (x + 1.0) / 2.0, SVG_MARGIN + inner * (y + 1.0) / 2.0)
+    };
+    let position = |v: usize| scale(positions[vertices.binary_search(&v).unwrap()]);
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{0}\" viewBox=\"0 0 {0} {0}\">",
+        SVG_SIZE
+    )
+    .unwrap();
+    out.push_str("<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+    let lightest = edges.iter().map(|&(i, j)| g.weight(i, j)).fold(f64::INFINITY, f64::min);
+    for &(i, j) in &edges {
+        let (x1, y1) = position(i);
+        let (x2, y2) = position(j);
+        writeln!(
+            out,
+            "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"black\" stroke-width=\"{:.2}\"/>",
+            x1, y1, x2, y2, g.weight(i, j) / lightest
+        )
+        .unwrap();
+    }
+    let degrees = weighted_degrees(g, &vertices, &edges);
+    for (&v, &d) in vertices.iter().zip(&degrees) {
+        let (x, y) = position(v);
+        writeln!(
+            out,
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{}\" fill=\"{}\" stroke=\"black\"/>",
+            x, y, VERTEX_RADIUS, degree_colour(d, &degrees)
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "<text x=\"{:.1}\" y=\"{:.1}\" font-family=\"sans-serif\" font-size=\"11\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>",
+            x, y, v
+        )
+        .unwrap();
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+// Positions in [-1, 1]^2
+fn circular_layout(n: usize) -> Vec<(f64, f64)> {
+    (0..n)
+        .map(|k| {
+            let angle = 2.0 * PI * k as f64 / n as f64 - PI / 2.0;
+            (angle.cos(), angle.sin())
+        })
+        .collect()
+}
+
+/*
+ * Fruchterman-Reingold with a linearly cooling temperature, rescaled to [-1, 1]^2.
+ * Starting from the circular layout keeps the result deterministic.
+ */
+fn spring_layout(vertices: &[usize], edges: &[(usize, usize)]) -> Vec<(f64, f64)> {
+    let n = vertices.len();
+    let mut pos = circular_layout(n);
+    if n < 2 {
+        return pos;
+    }
+    let index = |v: usize| vertices.binary_search(&v).unwrap();
+    let k = (4.0 / n as f64).sqrt();
+    for step in 0..SPRING_ITERATIONS {
+        let temperature = 0.1 * (1.0 - step as f64 / SPRING_ITERATIONS as f64);
+        let mut displacement = vec![(0.0, 0.0); n];
+        for a in 0..n {
+            for b in 0..n {
+                if a == b {
+                    continue;
+                }
+                let (dx, dy) = (pos[a].0 - pos[b].0, pos[a].1 - pos[b].1);
+                let d = (dx * dx + dy * dy).sqrt().max(1e-9);
+                let force = k * k / d;
+                displacement[a].0 += dx / d * force;
+                displacement[a].1 += dy / d * force;
+            }
+        }
+        for &(i, j) in edges {
+            let (a, b) = (index(i), index(j));
+            let (dx, dy) = (pos[a].0 - pos[b].0, pos[a].1 - pos[b].1);
+            let d = (dx * dx + dy * dy).sqrt().max(1e-9);
+            let force = d * d / k;
+            displacement[a].0 -= dx / d * force;
+            displacement[a].1 -= dy / d * force;
+            displacement[b].0 += dx / d * force;
+            displacement[b].1 += dy / d * force;
+        }
+        for (p, (dx, dy)) in pos.iter_mut().zip(displacement) {
+            let d = (dx * dx + dy * dy).sqrt().max(1e-9);
+            let step = d.min(temperature);
+            p.0 += dx / d * step;
+            p.1 += dy / d * step;
+        }
+    }
+
+    let (min_x, max_x) = pos.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| (lo.min(p.0), hi.max(p.0)));
+    let (min_y, max_y) = pos.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| (lo.min(p.1), hi.max(p.1)));
+    let extent = (max_x - min_x).max(max_y - min_y).max(1e-9);
+    pos.iter()
+        .map(|&(x, y)| {
+            (
+                2.0 * (x - (min_x + max_x) / 2.0) / extent,
+                2.0 * (y - (min_y + max_y) / 2.0) / extent,
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use crate::graph::Graph;
+
+/*
+ * Graph stored as compressed sparse rows, each row holding the neighbours of a vertex in
+ * increasing order together with the edge weights. Memory is O(n + m), and spanning trees are
+ * counted by a sparse LDL^T factorization of the reduced Laplacian, so searches with m in the
+ * hundreds avoid the O(n^3) cost of LapGraph.
+ */
+#[derive(Debug, Clone)]
+pub struct SparseGraph {
+    vertex_count: usize,
+    // Row v is neighbours[offsets[v]..offsets[v + 1]]
+    offsets: Vec<usize>,
+    neighbours: Vec<usize>,
+    weights: Vec<f64>,
+    // Sum of the weights of each row
+    weighted_degrees: Vec<f64>,
+}
+
+impl SparseGraph {
+    fn row(&self, v: usize) -> std::ops::Range<usize> {
+        self.offsets[v]..self.offsets[v + 1]
+    }
+
+    fn find(&self, i: usize, j: usize) -> Result<usize, usize> {
+        let row = self.row(i);
+        self.neighbours[row.clone()]
+            .binary_search(&j)
+            .map(|k| row.start + k)
+            .map_err(|k| row.start + k)
+    }
+
+    // Adds weight to the entry (i, j) of row i only
+    fn add_entry(&mut self, i: usize, j: usize, weight: f64) {
+        match self.find(i, j) {
+            Ok(k) => self.weights[k] += weight,
+            Err(k) => {
+                self.neighbours.insert(k, j);
+                self.weights.insert(k, weight);
+                for offset in &mut self.offsets[i + 1..] {
+                    *offset += 1;
+                }
+            }
+        }
+        self.weighted_degrees[i] += weight;
+    }
+
+    /*
+     * Pivots of the Laplacian with one vertex removed, over the non-isolated vertices, whose
+     * product is its determinant. None if the graph is disconnected, which is decided before any
+     * numeric work, so that every pivot is positive. Vertices are eliminated in minimum degree order, the Schur complements being kept as
+     * sparse rows, which is the LDL^T factorization with that ordering. The pivots are the
+     * diagonal of D and the last vertex left is the one removed.
+     */
+    fn reduced_laplacian_pivots(&self) -> Option<Vec<f64>> {
+        if !self.is_connected() {
+            return None;
+        }
+        let vertices: Vec<usize> = (0..self.vertex_count).filter(|&v| !self.row(v).is_empty()).collect();
+        let mut diagonal: HashMap<usize, f64> = vertices.iter().map(|&v| (v, self.weighted_degrees[v])).collect();
+        let mut rows: HashMap<usize, HashMap<usize, f64>> = vertices
+            .iter()
+            .map(|&v| (v, self.row(v).map(|k| (self.neighbours[k], -self.weights[k])).collect()))
+            .collect();
+
+        let mut pivots = Vec::with_capacity(vertices.len() - 1);
+        for _step in 1..vertices.len() {
+            let v = *rows
+                .iter()
+                .min_by_key(|(&v, row)| (row.len(), v))
+                .map(|(v, _)| v)
+                .unwrap();
+            let row = rows.remove(&v).unwrap();
+            let pivot = diagonal.remove(&v).unwrap();
+            pivots.push(pivot);
+            for (&u, &a) in &row {
+                rows.get_mut(&u).unwrap().remove(&v);
+                *diagonal.get_mut(&u).unwrap() -= a * a / pivot;
+                for (&w, &b) in &row {
+                    if w != u {
+                        *rows.get_mut(&u).unwrap().entry(w).or_insert(0.0) -= a * b / pivot;
+                    }
+                }
+            }
+        }
+        Some(pivots)
+    }
+}
+
+impl Graph for SparseGraph {
+    fn empty(vertex_count: usize) -> Self {
+        Self {
+            vertex_count,
+            offsets: vec![0; vertex_count + 1],
+            neighbours: vec![],
+            weights: vec![],
+            weighted_degrees: vec![0.0; vertex_count],
+        }
+    }
+
+    fn complete(vertex_count: usize) -> Self {
+        let mut g = Self::empty(vertex_count);
+        for i in 0..vertex_count {
+            g.neighbours.extend((0..vertex_count).filter(|&j| j != i));
+            g.offsets[i + 1] = g.neighbours.len();
+            g.weighted_degrees[i] = vertex_count as f64 - 1.0;
+        }
+        g.weights = vec![1.0; g.neighbours.len()];
+        g
+    }
+
+    fn add_edge(&mut self, i: usize, j: usize) {
+        debug_assert!(
+            i < self.vertex_count && j < self.vertex_count,
+            "SparseGraph::add_edge indices must lie in [0, {})",
+            self.vertex_count
+        );
+
+        debug_assert!(
+            self.find(i, j).is_err(),
+            "SparseGraph::add_edge does not support multi edges, see SparseGraph::add_parallel_edge"
+        );
+
+        self.add_parallel_edge(i, j);
+    }
+
+    fn add_parallel_edge(&mut self, i: usize, j: usize) {
+        self.add_weighted_edge(i, j, 1.0);
+    }
+
+    fn add_weighted_edge(&mut self, i: usize, j: usize, weight: f64) {
+        debug_assert!(i != j, "SparseGraph::add_weighted_edge does not support self loops");
+        debug_assert!(weight > 0.0, "SparseGraph::add_weighted_edge weights must be positive");
+
+        self.add_entry(i, j, weight);
+        self.add_entry(j, i, weight);
+    }
+
+    fn weight(&self, i: usize, j: usize) -> f64 {
+        self.find(i, j).map_or(0.0, |k| self.weights[k])
+    }
+
+    // Weights of multigraphs are integral, other weights are rounded
+    fn multiplicity(&self, i: usize, j: usize) -> usize {
+        self.weight(i, j).round() as usize
+    }
+
+    fn order(&self) -> usize {
+        (0..self.vertex_count).filter(|&v| !self.row(v).is_empty()).count()
+    }
+
+    fn size(&self) -> usize {
+        (self.weighted_degrees.iter().sum::<f64>() / 2.0) as usize
+    }
+
+    fn spanning_tree_count(&self) -> usize {
+        self.weighted_spanning_tree_count().round() as usize
+    }
+
+    fn weighted_spanning_tree_count(&self) -> f64 {
+        self.reduced_laplacian_pivots().map_or(0.0, |p| p.iter().product())
+    }
+
+    fn ln_spanning_tree_count(&self) -> f64 {
+        self.reduced_laplacian_pivots()
+            .map_or(f64::NEG_INFINITY, |p| p.iter().map(|x| x.ln()).sum())
+    }
+
+    fn degree(&self, vertex: usize) -> usize {
+        self.weighted_degrees[vertex].round() as usize
+    }
+
+    fn lowest_free_vertex(&self) -> Option<usize> {
+        (0..self.vertex_count).find(|&v| self.row(v).is_empty())
+    }
+
+    fn edges(&self) -> Vec<(usize, usize)> {
+        let mut edges = Vec::with_capacity(self.neighbours.len() / 2);
+        for i in 0..self.vertex_count {
+            for k in self.row(i) {
+                if self.neighbours[k] < i {
+                    edges.push((i, self.neighbours[k]));
+                }
+            }
+        }
+        edges
+    }
+}
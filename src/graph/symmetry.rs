@@ -0,0 +1,251 @@
+/*
+ * Symmetry of small multigraphs given as adjacency lists over 0..n, a neighbour being repeated
+ * for each copy of an edge, by colour refinement and individualization in the style of nauty.
+ */
+
+pub(crate) struct Symmetry<'a> {
+    adjacency: &'a [Vec<usize>],
+    // Multiplicity of each pair
+    adjacent: Vec<Vec<usize>>,
+}
+
+impl<'a> Symmetry<'a> {
+    pub(crate) fn new(adjacency: &'a [Vec<usize>]) -> Self {
+        let n = adjacency.len();
+        let mut adjacent = vec![vec![0; n]; n];
+        for (v, neighbours) in adjacency.iter().enumerate() {
+            for &u in neighbours {
+                adjacent[v][u] += 1;
+            }
+        }
+        Self { adjacency, adjacent }
+    }
+
+    /*
+     * Refines two colourings side by side until stable, colours being ranks of the signature
+     * (colour, sorted neighbour colours) among the signatures of both sides. Returns false if the
+     * sides split differently, in which case no automorphism maps one colouring to the other.
+     */
+    fn refine(&self, left: &mut [usize], right: &mut [usize]) -> bool {
+        let n = left.len();
+        let mut before = count_colours(left);
+        loop {
+            // Signatures of left are indexed by v and those of right by n + v
+            let mut signatures: Vec<(usize, Vec<usize>)> = Vec::with_capacity(2 * n);
+            for colours in [&*left, &*right] {
+                for v in 0..n {
+                    let mut around: Vec<usize> = self.adjacency[v].iter().map(|&u| colours[u]).collect();
+                    around.sort_unstable();
+                    signatures.push((colours[v], around));
+                }
+            }
+            let mut order: Vec<usize> = (0..2 * n).collect();
+            order.sort_unstable_by(|&a, &b| signatures[a].cmp(&signatures[b]));
+
+            // Each signature must be shared by as many vertices on both sides
+            let mut rank = 0;
+            let mut balance = 0isize;
+            for (k, &index) in order.iter().enumerate() {
+                if k > 0 && signatures[index] != signatures[order[k - 1]] {
+                    if balance != 0 {
+                        return false;
+                    }
+                    rank += 1;
+                }
+                if index < n {
+                    left[index] = rank;
+                    balance += 1;
+                } else {
+                    right[index - n] = rank;
+                    balance -= 1;
+                }
+            }
+            if balance != 0 {
+                return false;
+            }
+            let after = if n == 0 { 0 } else { rank + 1 };
+            if after == before {
+                return true;
+            }
+            before = after;
+        }
+    }
+
+    /*
+     * Whether some automorphism maps every vertex of colour c in left to one of colour c in right
+     */
+    fn extends(&self, left: &[usize], right: &[usize]) -> bool {
+        let Some(cell) = first_nontrivial_cell(left) else {
+            // Both colourings are discrete, so there is exactly one candidate map
+            let mut image = vec![0; left.len()];
+            for v in 0..left.len() {
+                image[v] = right.iter().position(|&c| c == left[v]).unwrap();
+            }
+            return self.is_automorphism(&image);
+        };
+        let x = left.iter().position(|&c| c == cell).unwrap();
+        let fresh = left.len();
+        for y in (0..right.len()).filter(|&y| right[y] == cell) {
+            let mut l = left.to_vec();
+            let mut r = right.to_vec();
+            l[x] = fresh;
+            r[y] = fresh;
+            if self.refine(&mut l, &mut r) && self.extends(&l, &r) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn is_automorphism(&self, image: &[usize]) -> bool {
+        self.adjacency
+            .iter()
+            .enumerate()
+            .all(|(v, neighbours)| {
+                neighbours
+                    .iter()
+                    .all(|&u| self.adjacent[image[v]][image[u]] == self.adjacent[v][u])
+            })
+    }
+
+    /*
+     * Size of the automorphism group as the product of orbit sizes along a stabilizer chain
+     */
+    pub(crate) fn automorphism_count(&self) -> u128 {
+        let n = self.adjacency.len();
+        let mut colours = vec![0; n];
+        let mut copy = colours.clone();
+        self.refine(&mut colours, &mut copy);
+        let mut count = 1;
+        while let Some(cell) = first_nontrivial_cell(&colours) {
+            let members: Vec<usize> = (0..n).filter(|&v| colours[v] == cell).collect();
+            let v = members[0];
+            let mut orbit = 1;
+            for &w in &members[1..] {
+                let mut l = colours.clone();
+                let mut r = colours.clone();
+                l[v] = n;
+                r[w] = n;
+                if self.refine(&mut l, &mut r) && self.extends(&l, &r) {
+                    orbit += 1;
+                }
+            }
+            count *= orbit;
+            colours[v] = n;
+            let mut copy = colours.clone();
+            self.refine(&mut colours, &mut copy);
+        }
+        count
+    }
+}
+
+fn count_colours(colours: &[usize]) -> usize {
+    let mut c = colours.to_vec();
+    c.sort_unstable();
+    c.dedup();
+    c.len()
+}
+
+// Smallest colour shared by several vertices
+fn first_nontrivial_cell(colours: &[usize]) -> Option<usize> {
+    let mut sizes = vec![0; colours.iter().max().map_or(0, |&c| c + 1)];
+    for &c in colours {
+        sizes[c] += 1;
+    }
+    sizes.iter().position(|&s| s > 1)
+}
+
+impl Symmetry<'_> {
+    /*
+     * Certificate which is equal for two multigraphs exactly when they are isomorphic: the least,
+     * over the leaves of the individualization-refinement tree, of the edge list relabelled by
+     * the leaf's discrete colouring. Branches equivalent under automorphisms found at earlier
+     * leaves are skipped, which keeps highly symmetric graphs such as cliques tractable.
+     */
+    pub(crate) fn canonical_form(&self) -> Vec<usize> {
+        let n = self.adjacency.len();
+        let mut colours = vec![0; n];
+        let mut copy = colours.clone();
+        self.refine(&mut colours, &mut copy);
+        let mut search = CanonicalSearch {
+            best: None,
+            automorphisms: vec![],
+        };
+        self.search_leaves(colours, &mut vec![], &mut search);
+        let mut form = vec![n];
+        form.extend(search.best.unwrap().0);
+        form
+    }
+
+    fn certificate(&self, colours: &[usize]) -> Vec<usize> {
+        let mut edges: Vec<(usize, usize)> = vec![];
+        for (v, neighbours) in self.adjacency.iter().enumerate() {
+            for &u in neighbours {
+                if colours[v] > colours[u] {
+                    edges.push((colours[v], colours[u]));
+                }
+            }
+        }
+        edges.sort_unstable();
+        edges.into_iter().flat_map(|(a, b)| [a, b]).collect()
+    }
+
+    fn search_leaves(&self, colours: Vec<usize>, prefix: &mut Vec<usize>, search: &mut CanonicalSearch) {
+        let n = colours.len();
+        let Some(cell) = first_nontrivial_cell(&colours) else {
+            let certificate = self.certificate(&colours);
+            match &search.best {
+                Some((best, labelling)) if *best == certificate => {
+                    // Both leaves give the same certificate, so they differ by an automorphism
+                    let mut inverse = vec![0; n];
+                    for (v, &c) in labelling.iter().enumerate() {
+                        inverse[c] = v;
+                    }
+                    search.automorphisms.push(colours.iter().map(|&c| inverse[c]).collect());
+                }
+                Some((best, _)) if *best < certificate => {}
+                _ => search.best = Some((certificate, colours)),
+            }
+            return;
+        };
+        let members: Vec<usize> = (0..n).filter(|&v| colours[v] == cell).collect();
+        let mut tried: Vec<usize> = vec![];
+        for &w in &members {
+            // Orbits of the automorphisms found so far which fix the prefix pointwise
+            let mut orbit: Vec<usize> = (0..n).collect();
+            for gamma in &search.automorphisms {
+                if prefix.iter().all(|&p| gamma[p] == p) {
+                    for (v, &image) in gamma.iter().enumerate() {
+                        let (a, b) = (find(&mut orbit, v), find(&mut orbit, image));
+                        orbit[a] = b;
+                    }
+                }
+            }
+            if tried.iter().any(|&t| find(&mut orbit, t) == find(&mut orbit, w)) {
+                continue;
+            }
+            tried.push(w);
+            let mut l = colours.clone();
+            l[w] = n;
+            let mut r = l.clone();
+            self.refine(&mut l, &mut r);
+            prefix.push(w);
+            self.search_leaves(l, prefix, search);
+            prefix.pop();
+        }
+    }
+}
+
+struct CanonicalSearch {
+    // Least certificate so far and the colouring of its leaf
+    best: Option<(Vec<usize>, Vec<usize>)>,
+    automorphisms: Vec<Vec<usize>>,
+}
+
+fn find(parent: &mut [usize], mut v: usize) -> usize {
+    while parent[v] != v {
+        parent[v] = parent[parent[v]];
+        v = parent[v];
+    }
+    v
+}
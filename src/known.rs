@@ -0,0 +1,98 @@
+/*
+ * Known optimal spanning tree counts t(m) over all simple graphs with m edges.
+ * Values were obtained by exhaustively enumerating connected graphs up to isomorphism.
+ */
+
+use crate::graph::Graph;
+
+#[derive(Debug, Clone, Copy)]
+pub struct KnownResult {
+    pub edge_count: usize,
+    pub spanning_trees: usize,
+    // A maximizer, as edges (i, j) with i > j
+    pub witness: &'static [(usize, usize)],
+}
+
+impl KnownResult {
+    pub fn witness_graph<G: Graph>(&self) -> G {
+        let n = self.witness.iter().map(|&(i, _)| i + 1).max().unwrap_or(0);
+        let mut g = G::empty(n);
+        for &(i, j) in self.witness {
+            g.add_edge(i, j);
+        }
+        g
+    }
+}
+
+pub const KNOWN_RESULTS: &[KnownResult] = &[
+    KnownResult { edge_count: 1, spanning_trees: 1, witness: &[(1, 0)] },
+    KnownResult { edge_count: 2, spanning_trees: 1, witness: &[(1, 0), (2, 0)] },
+    KnownResult { edge_count: 3, spanning_trees: 3, witness: &[(1, 0), (2, 0), (2, 1)] },
+    KnownResult { edge_count: 4, spanning_trees: 4, witness: &[(1, 0), (2, 0), (3, 1), (3, 2)] },
+    KnownResult {
+        edge_count: 5,
+        spanning_trees: 8,
+        witness: &[(1, 0), (2, 0), (2, 1), (3, 0), (3, 1)],
+    },
+    KnownResult {
+        edge_count: 6,
+        spanning_trees: 16,
+        witness: &[(1, 0), (2, 0), (2, 1), (3, 0), (3, 1), (3, 2)],
+    },
+    KnownResult {
+        edge_count: 7,
+        spanning_trees: 24,
+        witness: &[(1, 0), (2, 0), (2, 1), (3, 0), (3, 1), (4, 2), (4, 3)],
+    },
+    KnownResult {
+        edge_count: 8,
+        spanning_trees: 45,
+        witness: &[(1, 0), (2, 0), (2, 1), (3, 0), (3, 1), (4, 0), (4, 2), (4, 3)],
+    },
+    KnownResult {
+        edge_count: 9,
+        spanning_trees: 81,
+        witness: &[(1, 0), (2, 0), (3, 0), (4, 1), (4, 2), (4, 3), (5, 1), (5, 2), (5, 3)],
+    },
+    KnownResult {
+        edge_count: 10,
+        spanning_trees: 135,
+        witness: &[(1, 0), (2, 0), (2, 1), (3, 0), (3, 1), (4, 0), (4, 1), (5, 2), (5, 3), (5, 4)],
+    },
+    KnownResult {
+        edge_count: 11,
+        spanning_trees: 231,
+        witness: &[
+            (1, 0), (2, 0), (2, 1), (3, 0), (4, 0), (5, 1),
+            (5, 3), (5, 4), (6, 2), (6, 3), (6, 4),
+        ],
+    },
+    KnownResult {
+        edge_count: 12,
+        spanning_trees: 432,
+        witness: &[
+            (1, 0), (2, 0), (3, 0), (4, 1), (4, 2), (4, 3),
+            (5, 1), (5, 2), (5, 3), (6, 0), (6, 4), (6, 5),
+        ],
+    },
+];
+
+pub fn known_result(edge_count: usize) -> Option<&'static KnownResult> {
+    KNOWN_RESULTS.iter().find(|k| k.edge_count == edge_count)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Matches,
+    // A new record, only possible if the table value is not optimal
+    Exceeds,
+    Misses,
+}
+
+pub fn verdict(known: &KnownResult, found: usize) -> Verdict {
+    match found.cmp(&known.spanning_trees) {
+        std::cmp::Ordering::Equal => Verdict::Matches,
+        std::cmp::Ordering::Greater => Verdict::Exceeds,
+        std::cmp::Ordering::Less => Verdict::Misses,
+    }
+}
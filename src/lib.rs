@@ -0,0 +1,29 @@
+//! Search for graphs with a given number of edges maximizing the number of spanning trees.
+//!
+//! The building blocks are the [`Graph`] trait with its dense and sparse implementations
+//! [`LapGraph`] and [`SparseGraph`], the [`Game`] formulations of the search space ([`TreeGame`],
+//! which also covers multigraphs, [`DegreeGame`] and the weighted variant [`WeightGame`]) and the
+//! arenas which play them ([`MctsArena`] and [`BogoArena`]). Small edge counts can be settled
+//! outright by [`exhaustive::exhaustive`].
+
+pub mod graph;
+pub mod game;
+pub mod mcts;
+pub mod bogo;
+pub mod tree_game;
+pub mod weight_game;
+pub mod degree_game;
+pub mod known;
+pub mod exhaustive;
+#[cfg(feature = "python")]
+pub mod python;
+
+pub use crate::bogo::BogoArena;
+pub use crate::degree_game::{DegreeAction, DegreeGame};
+pub use crate::game::{Game, GameArena, GraphGame};
+pub use crate::graph::laplacian::LapGraph;
+pub use crate::graph::sparse::SparseGraph;
+pub use crate::graph::Graph;
+pub use crate::mcts::{AnyHeuristic, Heuristic, MctsArena, MctsNode, Puct, SpMcts, UCT};
+pub use crate::tree_game::{EdgeDecision, TreeGame};
+pub use crate::weight_game::{WeightAllocation, WeightGame};
@@ -1,15 +1,15 @@
-use clap::{Parser, Subcommand};
-use crate::mcts::{MctsArena, UCT};
-use crate::tree_game::TreeGame;
-use crate::bogo::BogoArena;
-use crate::game::GameArena;
-use crate::graph::laplacian::LapGraph;
-
-mod graph;
-mod mcts;
-mod tree_game;
-mod bogo;
-mod game;
+use std::time::{Duration, Instant};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use tree_hunter::{AnyHeuristic, BogoArena, DegreeGame, GameArena, Graph, GraphGame, Heuristic, LapGraph, MctsArena, Puct, SparseGraph, SpMcts, TreeGame, UCT, WeightGame};
+use tree_hunter::exhaustive::exhaustive;
+use tree_hunter::graph::analysis::analyze;
+use tree_hunter::graph::render::{self, Layout};
+use tree_hunter::known::{known_result, verdict, KnownResult, Verdict};
+use tree_hunter::mcts::prior::{DegreeBalancePrior, PriorPolicy};
+use tree_hunter::mcts::rollout::{DegreeBalanceRollout, EdgeBudgetRollout, RolloutPolicy, UniformRollout};
+use tree_hunter::mcts::hall_of_fame::HallOfFame;
+use tree_hunter::mcts::{Exploitation, Normalization};
+use tree_hunter::tree_game::default_vertex_count;
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
@@ -24,22 +24,44 @@ enum Commands {
     #[clap(visible_alias("mcts"))]
     MonteCarloTreeSearch {
         edge_count: usize,
-        upper_bound: usize,
+        /// Upper bound on the score, UCT normalizes by the observed rollouts if omitted
+        upper_bound: Option<usize>,
 
-        #[arg(short, long, default_value="0")]
-        vertex_count: usize,
+        #[command(flatten)]
+        search: SearchArgs,
+    },
 
-        #[arg(short, long, default_value="3.0")]
-        exploration_parameter: f64,
+    /// Check MCTS against the known optimal value for some edge count
+    Verify {
+        edge_count: usize,
 
-        #[arg(short, long, default_value="10")]
-        iterations: usize,
+        #[command(flatten)]
+        search: SearchArgs,
+    },
+
+    /// Distribute a weight budget over the pairs of vertices maximizing the weighted number of
+    /// spanning trees. Defaults are chosen as for an edge count equal to --units
+    #[clap(visible_alias("weights"))]
+    WeightSearch {
+        #[arg(value_parser = positive_budget)]
+        budget: f64,
+
+        /// Number of equal units the budget is split into
+        #[arg(long, default_value="12", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+        units: usize,
+
+        #[command(flatten)]
+        search: SearchArgs,
+    },
 
-        #[arg(short, long, default_value="false")]
-        verbose: bool,
+    /// Enumerate the connected graphs with some edge count up to isomorphism, giving the true
+    /// maximum and all maximizers to validate MCTS against
+    Exhaustive {
+        edge_count: usize,
 
+        /// Largest number of vertices, enough for every graph if 0
         #[arg(short, long, default_value="0")]
-        search_iterations: usize
+        vertex_count: usize,
     },
 
     /// Useful as a demonstration that MCTS is effective
@@ -58,41 +80,576 @@ enum Commands {
     }
 }
 
-fn run_mcts(m: usize, n: usize, bound: usize, exploration: f64, search_iterations: usize, verbose: bool) -> usize {
-    let b = 0;
-    let g = TreeGame::<LapGraph>::new(m, n);
-    let mut h = UCT::new(exploration);
-    h.set_upper_score_bound(bound as f64);
-    let mut a = MctsArena::new(g, h);
-    for _j in 1..=search_iterations {
-        for _i in 1..80 {
-            a.play_round();
+#[derive(Args, Clone)]
+struct SearchArgs {
+    #[arg(short, long, default_value="0")]
+    vertex_count: usize,
+
+    #[arg(short, long, default_value="3.0")]
+    exploration_parameter: f64,
+
+    #[arg(short, long, default_value="10")]
+    iterations: usize,
+
+    #[arg(long, default_value="false")]
+    verbose: bool,
+
+    #[arg(short, long, default_value="0")]
+    search_iterations: usize,
+
+    /// Choose a near-regular degree sequence before placing edges
+    #[arg(short, long, default_value="false")]
+    degree_first: bool,
+
+    /// Largest allowed difference between nonzero degrees when using --degree-first
+    #[arg(long, default_value="1")]
+    degree_spread: usize,
+
+    /// Heuristic used to select children during the search
+    #[arg(long, value_enum, default_value="max")]
+    selection: Selection,
+
+    /// Strength of the degree balancing prior used by PUCT, 0 gives uniform priors. PUCT with
+    /// --degree-first always uses uniform priors
+    #[arg(long, default_value="1.0")]
+    prior_strength: f64,
+
+    /// The constant D of SP-MCTS, added to the variance of rarely visited nodes
+    #[arg(long, default_value="1.0")]
+    deviation_bonus: f64,
+
+    /// Let UCT normalize by the observed rollouts instead of the upper bound
+    #[arg(long, default_value="false")]
+    self_normalize: bool,
+
+    /// Blend all-moves-as-first statistics into selection, with the given equivalence parameter
+    #[arg(long)]
+    rave: Option<f64>,
+
+    /// Policy choosing the moves of rollouts, only uniform is available with --degree-first.
+    /// The non-uniform policies work best with a vertex count close to that of a maximizer
+    #[arg(long, value_enum, default_value="uniform")]
+    rollout: Rollout,
+
+    /// Probability of a uniformly random move in degree-balance rollouts
+    #[arg(long, default_value="0.1")]
+    rollout_epsilon: f64,
+
+    /// Rollouts per expansion
+    #[arg(long, default_value="50", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    rollouts: usize,
+
+    /// Rounds played between prunes of the search tree
+    #[arg(long, default_value="79", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    rounds: usize,
+
+    /// Scale the rollouts per expansion by the rollout variance of the expanded node
+    #[arg(long, default_value="false")]
+    adaptive_rollouts: bool,
+
+    /// Fewest rollouts per expansion with --adaptive-rollouts
+    #[arg(long, default_value="10", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    min_rollouts: usize,
+
+    /// Most rollouts per expansion with --adaptive-rollouts
+    #[arg(long, default_value="200", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    max_rollouts: usize,
+
+    /// Print search statistics every this many seconds
+    #[arg(long, value_name = "SECONDS")]
+    progress: Option<f64>,
+
+    /// Write the top of the search tree to this file at the end of the search (last iteration
+    /// wins), as JSON if the name ends in .json and as Graphviz DOT otherwise
+    #[arg(long, value_name = "PATH")]
+    export_tree: Option<std::path::PathBuf>,
+
+    /// Levels of the search tree below the root written by --export-tree
+    #[arg(long, default_value="3")]
+    export_depth: usize,
+
+    /// Draw the overall best graph of all iterations to this file, as SVG if the name ends in
+    /// .svg and as Graphviz DOT otherwise
+    #[arg(long, value_name = "PATH")]
+    render: Option<std::path::PathBuf>,
+
+    /// Vertex placement for SVG rendering
+    #[arg(long, value_enum, default_value_t = RenderLayout::Circular)]
+    layout: RenderLayout,
+
+    /// Search multigraphs in which each pair of vertices is joined by up to this many edges, the
+    /// edge-budget and degree-balance rollouts only ever adding single edges
+    #[arg(long, default_value="1")]
+    multiplicity: usize,
+
+    /// Store graphs sparsely and count spanning trees by sparse factorization, faster for large m
+    #[arg(long, default_value="false")]
+    sparse: bool,
+
+    /// Score graphs by ln of their spanning tree count, the upper bound being converted to match
+    #[arg(long, default_value="false")]
+    log_score: bool,
+
+    /// Remember the score of every graph up to isomorphism instead of recounting spanning trees
+    #[arg(long, default_value="false")]
+    memo: bool,
+
+    /// Best graphs kept up to isomorphism and reported over all iterations, 0 to disable
+    #[arg(long, default_value="5")]
+    hall_of_fame: usize,
+}
+
+impl SearchArgs {
+    fn resolve_defaults(&mut self, m: usize) {
+        if self.vertex_count == 0 {
+            self.vertex_count = default_vertex_count(m);
+        }
+        if self.search_iterations == 0 {
+            self.search_iterations = default_search_iterations(m);
         }
-        if a.prune() {
-            println!("Algorithm terminated by searching all possible graphs. Best graph has {} spanning trees", b);
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy)]
+enum Selection {
+    /// UCT on the best rollout of each node
+    #[value(alias("uct"))]
+    Max,
+    /// UCT on the mean rollout of each node
+    Mean,
+    /// Single-player MCTS, mean based UCT with a variance term
+    SpMcts,
+    /// PUCT with priors, see --prior-strength
+    Puct,
+}
+
+#[derive(ValueEnum, Clone, Copy)]
+enum Rollout {
+    /// Uniformly random moves
+    Uniform,
+    /// Add each edge with probability remaining edges / remaining pairs
+    EdgeBudget,
+    /// Greedily add edges between vertices below the average degree
+    DegreeBalance,
+}
+
+#[derive(ValueEnum, Clone, Copy)]
+enum RenderLayout {
+    /// Vertices evenly spaced on a circle
+    Circular,
+    /// Force directed placement
+    Spring,
+}
+
+// Outcome of one search, aggregated over the iterations by report_runs
+struct RunResult {
+    // Best score, ln of the spanning tree count with --log-score
+    score: f64,
+    graph: LapGraph,
+    elapsed: Duration,
+    time_to_best: Option<Duration>,
+    // None for random search
+    tree_size: Option<usize>,
+}
+
+fn run_mcts<G, H, P, R>(mut a: MctsArena<G, H, P, R>, args: &SearchArgs, hall: &mut HallOfFame<LapGraph>) -> RunResult
+where
+    G: GraphGame,
+    H: Heuristic,
+    P: PriorPolicy<G>,
+    R: RolloutPolicy<G>,
+{
+    let mut last_progress = 0.0;
+    for _j in 1..=args.search_iterations {
+        if a.run_iteration() {
+            let prefix = if args.log_score { "e^" } else { "" };
+            println!("Algorithm terminated by searching all possible graphs. Best graph has {}{} spanning trees", prefix, a.best());
             break;
         }
+        if let Some(interval) = args.progress {
+            let elapsed = a.stats().elapsed().as_secs_f64();
+            if elapsed - last_progress >= interval {
+                last_progress = elapsed;
+                print_progress(&a);
+            }
+        }
+    }
+    if let Some(path) = &args.export_tree {
+        let tree = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => a.to_json(args.export_depth),
+            _ => a.to_dot(args.export_depth),
+        };
+        if let Err(e) = std::fs::write(path, tree) {
+            eprintln!("Could not write {}: {}", path.display(), e);
+        }
     }
-    if verbose {
+    if args.verbose {
+        print_stats(&a, args);
         println!("Search Tree Size {}", a.tree_size());
-        println!("Predicted Value {}", a.best() as usize);
+        if args.log_score {
+            println!("Predicted Value e^{}", a.best());
+        } else {
+            println!("Predicted Value {}", a.best());
+        }
         let q = a.best_game();
         print!("Graph Edges: ");
-        q.print_graph();
+        q.graph().print_edges();
+        println!("{}", analyze(q.graph()));
+    }
+    let hall_of_fame = a.hall_of_fame().map(|g| to_lap_graph(g.graph()));
+    if args.verbose && !hall_of_fame.is_empty() {
+        print_hall_of_fame(&hall_of_fame, args);
+    }
+    hall.merge(hall_of_fame);
+    RunResult {
+        score: a.best(),
+        graph: to_lap_graph(a.best_game().graph()),
+        elapsed: a.stats().elapsed(),
+        time_to_best: a.stats().time_to_best(),
+        tree_size: Some(a.tree_size()),
+    }
+}
+
+// Copies a graph into a LapGraph, so that the results of every representation can be merged
+fn to_lap_graph<G: Graph>(g: &G) -> LapGraph {
+    let edges = g.edges();
+    let n = edges.iter().map(|&(i, _)| i + 1).max().unwrap_or(0);
+    let mut h = LapGraph::empty(n);
+    for (i, j) in edges {
+        h.add_weighted_edge(i, j, g.weight(i, j));
+    }
+    h
+}
+
+// Draws the overall best graph of the runs to --render
+fn render_best(runs: &[RunResult], args: &SearchArgs) {
+    let (Some(path), Some((_, best))) = (&args.render, best_run(runs)) else {
+        return;
+    };
+    let drawing = match path.extension().and_then(|e| e.to_str()) {
+        Some("svg") => render::to_svg(&best.graph, match args.layout {
+            RenderLayout::Circular => Layout::Circular,
+            RenderLayout::Spring => Layout::Spring,
+        }),
+        _ => render::to_dot(&best.graph),
+    };
+    if let Err(e) = std::fs::write(path, drawing) {
+        eprintln!("Could not write {}: {}", path.display(), e);
+    }
+}
+
+fn print_hall_of_fame(hall: &HallOfFame<LapGraph>, args: &SearchArgs) {
+    println!("Hall of Fame");
+    for (rank, (score, g)) in hall.iter().enumerate() {
+        print!("{}. {} ", rank + 1, format_score(score, args.log_score));
+        g.print_edges();
+    }
+}
+
+// Weighted counts and logarithms of equal graphs may differ in the last bits
+fn tolerance(score: f64) -> f64 {
+    1e-9 * score.abs().max(1.0)
+}
+
+// Counts within tolerance of an integer print as integers, other weighted counts and logarithms
+// are rounded
+fn format_score(score: f64, log_score: bool) -> String {
+    if log_score {
+        format!("e^{score:.4}")
+    } else if (score - score.round()).abs() <= tolerance(score) {
+        format!("{}", score.round())
+    } else {
+        format!("{score:.4}")
+    }
+}
+
+fn print_progress<G, H, P, R>(a: &MctsArena<G, H, P, R>)
+where
+    G: GraphGame,
+    H: Heuristic,
+    P: PriorPolicy<G>,
+    R: RolloutPolicy<G>,
+{
+    let stats = a.stats();
+    let depths = a.depth_distribution();
+    println!(
+        "[{:.1}s] best {} (at {:.1}s), {:.0} rollouts/s, {} nodes, depth {} (mean {:.1}), {} pruned",
+        stats.elapsed().as_secs_f64(),
+        a.best(),
+        stats.time_to_best().unwrap_or_default().as_secs_f64(),
+        stats.rollouts_per_second(),
+        a.tree_size(),
+        depths.max_depth(),
+        depths.mean_depth(),
+        stats.nodes_pruned(),
+    );
+}
+
+fn print_stats<G, H, P, R>(a: &MctsArena<G, H, P, R>, args: &SearchArgs)
+where
+    G: GraphGame,
+    H: Heuristic,
+    P: PriorPolicy<G>,
+    R: RolloutPolicy<G>,
+{
+    let stats = a.stats();
+    println!("Search Time {:.2}s", stats.elapsed().as_secs_f64());
+    println!("Rounds {}, Rollouts {} ({:.0}/s)", stats.rounds(), stats.rollouts(), stats.rollouts_per_second());
+    println!("Nodes Pruned {}", stats.nodes_pruned());
+    if args.memo {
+        println!("Memo Hits {}", stats.memo_hits());
+    }
+    println!("Nodes per Depth {:?}", a.depth_distribution().counts());
+    print!("Best over Time:");
+    for (t, score) in stats.best_history() {
+        print!(" {}@{:.3}s", score, t.as_secs_f64());
+    }
+    println!();
+}
+
+// Only UCT may go without a bound, see Commands::MonteCarloTreeSearch
+fn heuristic(bound: Option<usize>, args: &SearchArgs) -> AnyHeuristic {
+    // Bounds are compared to scores, so they live in log space along with them
+    let bound = bound.map(|b| if args.log_score { (b as f64).ln() } else { b as f64 });
+    match args.selection {
+        Selection::Max | Selection::Mean => {
+            let mut h = UCT::new(args.exploration_parameter);
+            match bound {
+                Some(bound) if !args.self_normalize => h.set_upper_score_bound(bound),
+                _ => h.set_normalization(Normalization::Observed),
+            }
+            if let Selection::Mean = args.selection {
+                h.set_exploitation(Exploitation::Mean);
+            }
+            AnyHeuristic::Uct(h)
+        }
+        Selection::SpMcts => {
+            let mut h = SpMcts::new(args.exploration_parameter, args.deviation_bonus);
+            h.set_upper_score_bound(bound.unwrap());
+            AnyHeuristic::SpMcts(h)
+        }
+        Selection::Puct => {
+            let mut h = Puct::new(args.exploration_parameter);
+            h.set_upper_score_bound(bound.unwrap());
+            AnyHeuristic::Puct(h)
+        }
+    }
+}
+
+fn configure<G, H, P, R>(a: &mut MctsArena<G, H, P, R>, args: &SearchArgs)
+where
+    G: GraphGame,
+    H: Heuristic,
+    P: PriorPolicy<G>,
+    R: RolloutPolicy<G>,
+{
+    if let Some(equivalence) = args.rave {
+        a.enable_rave(equivalence);
+    }
+    if args.memo {
+        a.enable_score_memo();
     }
-    a.best() as usize
+    a.set_hall_of_fame(args.hall_of_fame);
+    a.set_num_rollouts(args.rollouts);
+    a.set_rounds_per_iteration(args.rounds);
+    if args.adaptive_rollouts {
+        a.set_adaptive_rollouts(args.min_rollouts, args.max_rollouts);
+    }
+}
+
+fn search(m: usize, bound: Option<usize>, args: &SearchArgs, hall: &mut HallOfFame<LapGraph>) -> RunResult {
+    if args.sparse {
+        search_with::<SparseGraph>(m, bound, args, hall)
+    } else {
+        search_with::<LapGraph>(m, bound, args, hall)
+    }
+}
+
+fn search_with<Gr: Graph>(m: usize, bound: Option<usize>, args: &SearchArgs, hall: &mut HallOfFame<LapGraph>) -> RunResult {
+    let n = args.vertex_count;
+    let h = heuristic(bound, args);
+    if args.degree_first {
+        let mut a = MctsArena::new(DegreeGame::<Gr>::new(m, n, args.degree_spread), h);
+        configure(&mut a, args);
+        run_mcts(a, args, hall)
+    } else {
+        // Priors only steer PUCT, other heuristics keep uniform expansion
+        let strength = match args.selection {
+            Selection::Puct => args.prior_strength,
+            _ => 0.0,
+        };
+        let mut g = TreeGame::<Gr>::new(m, n);
+        g.set_log_score(args.log_score);
+        g.set_max_multiplicity(args.multiplicity);
+        let prior = DegreeBalancePrior::new(strength);
+        match args.rollout {
+            Rollout::Uniform => run_tree(g, h, prior, UniformRollout, args, hall),
+            Rollout::EdgeBudget => run_tree(g, h, prior, EdgeBudgetRollout, args, hall),
+            Rollout::DegreeBalance => {
+                run_tree(g, h, prior, DegreeBalanceRollout::new(args.rollout_epsilon), args, hall)
+            }
+        }
+    }
+}
+
+fn run_tree<Gr: Graph, R: RolloutPolicy<TreeGame<Gr>>>(
+    g: TreeGame<Gr>,
+    h: AnyHeuristic,
+    prior: DegreeBalancePrior,
+    rollout: R,
+    args: &SearchArgs,
+    hall: &mut HallOfFame<LapGraph>,
+) -> RunResult {
+    let mut a = MctsArena::with_policies(g, h, prior, rollout);
+    configure(&mut a, args);
+    run_mcts(a, args, hall)
 }
 
-fn run_bogo(m: usize, n: usize, search_iterations: usize) -> usize {
+fn run_bogo(m: usize, n: usize, search_iterations: usize) -> RunResult {
+    let start = Instant::now();
+    let mut time_to_best = None;
     let g = TreeGame::<LapGraph>::new(m, n);
     let mut a = BogoArena::new(g);
     for _j in 1..=search_iterations {
+        let before = a.best();
         for _i in 1..80 {
             a.play_round();
         }
+        if a.best() > before {
+            time_to_best = Some(start.elapsed());
+        }
         println!("{}", a.best());
     }
-    a.best() as usize
+    RunResult {
+        score: a.best(),
+        graph: a.best_game().graph().clone(),
+        elapsed: start.elapsed(),
+        time_to_best,
+        tree_size: None,
+    }
+}
+
+fn check_rollout(args: &SearchArgs) {
+    if args.adaptive_rollouts && args.min_rollouts > args.max_rollouts {
+        eprintln!("--min-rollouts may not exceed --max-rollouts");
+        std::process::exit(2);
+    }
+    if args.degree_first && !matches!(args.rollout, Rollout::Uniform) {
+        eprintln!("Only uniform rollouts are available with --degree-first");
+        std::process::exit(2);
+    }
+    if args.multiplicity > 1 && args.degree_first {
+        eprintln!("Multigraphs are not available with --degree-first");
+        std::process::exit(2);
+    }
+    if args.log_score && args.degree_first {
+        eprintln!("Log scores are not available with --degree-first");
+        std::process::exit(2);
+    }
+}
+
+fn positive_budget(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(budget) if budget > 0.0 && budget.is_finite() => Ok(budget),
+        Ok(_) => Err("the budget must be positive".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/*
+ * Weight search plays WeightGame on dense graphs with uniform rollouts and plain scores, the options
+ * of the edge games would be ignored
+ */
+fn check_weight_search(args: &SearchArgs) {
+    if !matches!(args.selection, Selection::Max | Selection::Mean) {
+        eprintln!("Only UCT selection is available for weight search");
+        std::process::exit(2);
+    }
+    if !matches!(args.rollout, Rollout::Uniform) {
+        eprintln!("Only uniform rollouts are available for weight search");
+        std::process::exit(2);
+    }
+    let ignored = [
+        ("--degree-first", args.degree_first),
+        ("--degree-spread", args.degree_spread != 1),
+        ("--prior-strength", args.prior_strength != 1.0),
+        ("--multiplicity", args.multiplicity != 1),
+        ("--sparse", args.sparse),
+        ("--log-score", args.log_score),
+    ];
+    if let Some((flag, _)) = ignored.iter().find(|&&(_, set)| set) {
+        eprintln!("{} is not available for weight search", flag);
+        std::process::exit(2);
+    }
+}
+
+// Doubling every two edges, capped from 42 edges on so that large edge counts still get a run
+fn default_search_iterations(m: usize) -> usize {
+    const MAX_EXPONENT: usize = 20;
+    2usize.pow((m / 2).saturating_sub(1).min(MAX_EXPONENT) as u32)
+}
+
+// Mean, median, minimum and maximum
+fn summarize(mut values: Vec<f64>) -> (f64, f64, f64, f64) {
+    values.sort_by(f64::total_cmp);
+    let n = values.len();
+    let mean = values.iter().sum::<f64>() / n as f64;
+    let median = if n % 2 == 1 {
+        values[n / 2]
+    } else {
+        (values[n / 2 - 1] + values[n / 2]) / 2.0
+    };
+    (mean, median, values[0], values[n - 1])
+}
+
+// The first run reaching the maximum
+fn best_run(runs: &[RunResult]) -> Option<(usize, &RunResult)> {
+    runs.iter()
+        .enumerate()
+        .rev()
+        .max_by(|(_, a), (_, b)| a.score.total_cmp(&b.score))
+}
+
+/*
+ * Aggregates the iterations of a search. Success is measured against the known optimum when there
+ * is one, otherwise against the best run
+ */
+fn report_runs(runs: &[RunResult], known: Option<&KnownResult>, log_score: bool) {
+    if runs.is_empty() {
+        return;
+    }
+    let format = |s| format_score(s, log_score);
+    let scores: Vec<f64> = runs.iter().map(|r| r.score).collect();
+    let (mean, median, min, max) = summarize(scores.clone());
+    println!("Best score: mean {}, median {}, min {}, max {}", format(mean), format(median), format(min), format(max));
+
+    let times: Vec<f64> = runs.iter().filter_map(|r| r.time_to_best).map(|t| t.as_secs_f64()).collect();
+    if !times.is_empty() {
+        let (mean, median, min, max) = summarize(times);
+        println!("Time to best: mean {mean:.3}s, median {median:.3}s, min {min:.3}s, max {max:.3}s");
+    }
+    let total: Duration = runs.iter().map(|r| r.elapsed).sum();
+    println!("Total search time {:.2}s", total.as_secs_f64());
+    let sizes: Vec<f64> = runs.iter().filter_map(|r| r.tree_size).map(|s| s as f64).collect();
+    if !sizes.is_empty() {
+        let (mean, _, min, max) = summarize(sizes);
+        println!("Search tree size: mean {mean:.0}, min {min}, max {max}");
+    }
+
+    let (index, best) = best_run(runs).unwrap();
+    let (target, label) = match known {
+        Some(known) => {
+            println!("Best value {}, known optimum {}", best.graph.spanning_tree_count(), known.spanning_trees);
+            let target = known.spanning_trees as f64;
+            (if log_score { target.ln() } else { target }, known.spanning_trees.to_string())
+        }
+        None => (max, format(max)),
+    };
+    let count = scores.iter().filter(|&&s| s >= target - tolerance(target)).count();
+    println!("Value {} achieved in {count}/{} iterations ({}%)", label, runs.len(),
+             100.0 * (count as f64/(runs.len() as f64)));
+    print!("Overall best graph (iteration {}): ", index + 1);
+    best.graph.print_edges();
 }
 
 fn main() {
@@ -101,38 +658,126 @@ fn main() {
         Some(Commands::MonteCarloTreeSearch {
                 edge_count,
                 upper_bound,
-                mut vertex_count,
-                exploration_parameter,
-                iterations,
-                verbose,
-                mut search_iterations
+                search: args
              }) => {
-            if vertex_count == 0 {
-                vertex_count = *edge_count;
-            }
-            if search_iterations == 0 {
-                search_iterations = 2usize.pow((*edge_count/2 - 1) as u32);
+            let mut args = args.clone();
+            args.resolve_defaults(*edge_count);
+            if upper_bound.is_none() && matches!(args.selection, Selection::SpMcts | Selection::Puct) {
+                eprintln!("An upper bound is required unless selecting with UCT");
+                std::process::exit(2);
             }
+            check_rollout(&args);
             println!("Performing Monte-Carlo Tree Search");
-            let mut v = Vec::with_capacity(*iterations);
-            for i in 0..*iterations {
-                if *verbose {
+            let mut runs = Vec::with_capacity(args.iterations);
+            let mut hall = HallOfFame::new(args.hall_of_fame);
+            for i in 0..args.iterations {
+                if args.verbose {
                     println!("---------------");
                 }
                 println!("Iteration {}", i + 1);
-                v.push(run_mcts(
-                    *edge_count,
-                    vertex_count,
-                    *upper_bound,
-                    *exploration_parameter,
-                    search_iterations,
-                    *verbose,
-                ));
+                runs.push(search(*edge_count, *upper_bound, &args, &mut hall));
+            }
+            report_runs(&runs, known_result(*edge_count).filter(|_| args.multiplicity <= 1), args.log_score);
+            if !hall.is_empty() {
+                print_hall_of_fame(&hall, &args);
+            }
+            render_best(&runs, &args);
+        }
+
+        Some(Commands::Verify {
+                edge_count,
+                search: args
+             }) => {
+            let Some(known) = known_result(*edge_count) else {
+                eprintln!("No known result for {} edges", edge_count);
+                std::process::exit(2);
+            };
+            let mut args = args.clone();
+            args.resolve_defaults(*edge_count);
+            check_rollout(&args);
+            if args.multiplicity > 1 {
+                eprintln!("Known results are for simple graphs only");
+                std::process::exit(2);
+            }
+            println!("Verifying t({}) = {}", edge_count, known.spanning_trees);
+            let mut runs = Vec::with_capacity(args.iterations);
+            let mut hall = HallOfFame::new(args.hall_of_fame);
+            for i in 0..args.iterations {
+                if args.verbose {
+                    println!("---------------");
+                }
+                let run = search(*edge_count, Some(known.spanning_trees), &args, &mut hall);
+                let best = run.graph.spanning_tree_count();
+                match verdict(known, best) {
+                    Verdict::Matches => println!("Iteration {}: {} matches", i + 1, best),
+                    Verdict::Exceeds => println!("Iteration {}: {} exceeds the known value (new record!)", i + 1, best),
+                    Verdict::Misses => println!("Iteration {}: {} misses", i + 1, best),
+                }
+                runs.push(run);
+            }
+            report_runs(&runs, Some(known), args.log_score);
+            if !hall.is_empty() {
+                print_hall_of_fame(&hall, &args);
+            }
+            render_best(&runs, &args);
+            let best = runs.iter().map(|r| r.graph.spanning_tree_count()).max().unwrap_or(0);
+            if verdict(known, best) == Verdict::Misses {
+                std::process::exit(1);
+            }
+        }
+
+        Some(Commands::WeightSearch {
+                budget,
+                units,
+                search: args
+             }) => {
+            let mut args = args.clone();
+            args.resolve_defaults(*units);
+            check_weight_search(&args);
+            check_rollout(&args);
+            println!("Performing Monte-Carlo Tree Search over weightings");
+            let mut runs = Vec::with_capacity(args.iterations);
+            let mut hall = HallOfFame::new(args.hall_of_fame);
+            for i in 0..args.iterations {
+                if args.verbose {
+                    println!("---------------");
+                }
+                let g = WeightGame::<LapGraph>::complete(args.vertex_count, *budget, *units);
+                let mut a = MctsArena::new(g, heuristic(None, &args));
+                configure(&mut a, &args);
+                let run = run_mcts(a, &args, &mut hall);
+                println!("Iteration {}: {}", i + 1, format_score(run.score, false));
+                runs.push(run);
+            }
+            report_runs(&runs, None, false);
+            if !hall.is_empty() {
+                print_hall_of_fame(&hall, &args);
+            }
+            render_best(&runs, &args);
+        }
+
+        Some(Commands::Exhaustive {
+                edge_count,
+                mut vertex_count
+             }) => {
+            if vertex_count == 0 {
+                vertex_count = edge_count + 1;
+            }
+            println!("Enumerating connected graphs with {} edges", edge_count);
+            let result = exhaustive::<LapGraph>(*edge_count, vertex_count);
+            println!("{} non-isomorphic graphs, {} of them with the maximum of {} spanning trees",
+                     result.graphs, result.maximizers.len(), result.spanning_trees);
+            for g in &result.maximizers {
+                g.print_edges();
+            }
+            // Too few vertices leave nothing to compare
+            if let Some(known) = known_result(*edge_count).filter(|_| result.graphs > 0) {
+                match verdict(known, result.spanning_trees) {
+                    Verdict::Matches => println!("Matches the known optimum {}", known.spanning_trees),
+                    Verdict::Exceeds => println!("Exceeds the known optimum {}", known.spanning_trees),
+                    Verdict::Misses => println!("Misses the known optimum {}", known.spanning_trees),
+                }
             }
-            let b = v.iter().max().unwrap();
-            let count = v.iter().filter(|&s| *s == *b).count();
-            println!("Value {} achieved in {count}/{} iterations ({}%)", b, *iterations,
-                     100.0 * (count as f64/(*iterations as f64)));
         }
 
         Some(Commands::BogoSearch {
@@ -142,25 +787,22 @@ fn main() {
                  mut search_iterations
              }) => {
             if vertex_count == 0 {
-                vertex_count = *edge_count;
+                vertex_count = default_vertex_count(*edge_count);
             }
             if search_iterations == 0 {
-                search_iterations = 2usize.pow((*edge_count/2 - 1) as u32);
+                search_iterations = default_search_iterations(*edge_count);
             }
             println!("Performing BogoSearch");
-            let mut v = Vec::with_capacity(*iterations);
+            let mut runs = Vec::with_capacity(*iterations);
             for i in 0..*iterations {
                 println!("Iteration {i}");
-                v.push(run_bogo(
+                runs.push(run_bogo(
                     *edge_count,
                     vertex_count,
                     search_iterations,
                 ));
             }
-            let b = v.iter().max().unwrap();
-            let count = v.iter().filter(|&s| *s == *b).count();
-            println!("Value {} achieved in {count}/{} iterations ({}%)", b, *iterations,
-                     100.0 * (count as f64/(*iterations as f64)));
+            report_runs(&runs, known_result(*edge_count), false);
         }
         None => {}
     }
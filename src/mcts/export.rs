@@ -0,0 +1,95 @@
+use std::fmt::{Debug, Write};
+use indextree::{Arena, NodeId};
+use crate::mcts::MctsNode;
+
+/*
+ * Export of the top levels of a search tree for inspection, see MctsArena::to_dot and
+ * MctsArena::to_json. Nodes are labelled by the Debug form of their action, their visit count and
+ * their best and mean rollout. The root has depth 0.
+ */
+
+fn action_label<A: Debug>(node: &MctsNode<A>) -> String {
+    match &node.action {
+        Some(action) => format!("{:?}", action),
+        None => "root".to_string(),
+    }
+}
+
+fn mean_rollout<A>(node: &MctsNode<A>) -> Option<f64> {
+    (node.num_simulations > 0.0).then(|| node.total_accumulation / node.num_simulations)
+}
+
+// Escapes quotes and backslashes, which is enough for Debug output in both DOT and JSON strings
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+pub(crate) fn to_dot<A: Debug>(arena: &Arena<MctsNode<A>>, root: NodeId, depth: usize) -> String {
+    let mut out = String::from("digraph mcts {\n    node [shape=box, fontname=monospace];\n");
+    let mut next_id = 0;
+    write_dot(arena, root, depth, &mut next_id, &mut out);
+    out.push_str("}\n");
+    out
+}
+
+// Writes the subtree below node_id and returns the DOT id given to node_id
+fn write_dot<A: Debug>(
+    arena: &Arena<MctsNode<A>>,
+    node_id: NodeId,
+    depth: usize,
+    next_id: &mut usize,
+    out: &mut String,
+) -> usize {
+    let id = *next_id;
+    *next_id += 1;
+    let node = arena[node_id].get();
+    let mean = mean_rollout(node).map_or("-".to_string(), |m| format!("{:.2}", m));
+    writeln!(
+        out,
+        "    n{} [label=\"{}\\nvisits {}\\nbest {}\\nmean {}\"];",
+        id,
+        escape(&action_label(node)),
+        node.num_simulations,
+        node.best_rollout,
+        mean
+    )
+    .unwrap();
+    if depth > 0 {
+        for child in node_id.children(arena) {
+            let child_id = write_dot(arena, child, depth - 1, next_id, out);
+            writeln!(out, "    n{} -> n{};", id, child_id).unwrap();
+        }
+    }
+    id
+}
+
+pub(crate) fn to_json<A: Debug>(arena: &Arena<MctsNode<A>>, root: NodeId, depth: usize) -> String {
+    let mut out = String::new();
+    write_json(arena, root, depth, &mut out);
+    out.push('\n');
+    out
+}
+
+fn write_json<A: Debug>(arena: &Arena<MctsNode<A>>, node_id: NodeId, depth: usize, out: &mut String) {
+    let node = arena[node_id].get();
+    let action = match &node.action {
+        Some(_) => format!("\"{}\"", escape(&action_label(node))),
+        None => "null".to_string(),
+    };
+    let mean = mean_rollout(node).map_or("null".to_string(), |m| m.to_string());
+    write!(
+        out,
+        "{{\"action\":{},\"visits\":{},\"best\":{},\"mean\":{},\"children\":[",
+        action, node.num_simulations, node.best_rollout, mean
+    )
+    .unwrap();
+    if depth > 0 {
+        for (index, child) in node_id.children(arena).enumerate() {
+            if index > 0 {
+                out.push(',');
+            }
+            write_json(arena, child, depth - 1, out);
+        }
+    }
+    out.push_str("]}");
+}
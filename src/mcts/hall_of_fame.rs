@@ -0,0 +1,76 @@
+/*
+ * The best distinct items seen so far, at most capacity of them, best first with ties in order of
+ * arrival. Items with equal keys, such as isomorphic graphs by Game::score_key, are kept once and
+ * items without a key are always distinct. Keys and items are only computed for scores which
+ * would enter, so offering every rollout stays cheap once the hall is full.
+ */
+#[derive(Debug, Clone)]
+pub struct HallOfFame<T> {
+    capacity: usize,
+    entries: Vec<(f64, Option<Vec<usize>>, T)>,
+}
+
+impl<T> HallOfFame<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: vec![],
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // Whether an item with this score would be considered at all
+    fn admits(&self, score: f64) -> bool {
+        self.entries.len() < self.capacity || self.entries.last().is_some_and(|&(s, _, _)| score > s)
+    }
+
+    // True if the item entered the hall
+    pub fn offer(&mut self, score: f64, key: impl FnOnce() -> Option<Vec<usize>>, item: impl FnOnce() -> T) -> bool {
+        if !self.admits(score) {
+            return false;
+        }
+        self.insert(score, key(), item)
+    }
+
+    fn insert(&mut self, score: f64, key: Option<Vec<usize>>, item: impl FnOnce() -> T) -> bool {
+        if key.is_some() && self.entries.iter().any(|(_, k, _)| *k == key) {
+            return false;
+        }
+        let position = self.entries.partition_point(|&(s, _, _)| s >= score);
+        self.entries.insert(position, (score, key, item()));
+        self.entries.truncate(self.capacity);
+        true
+    }
+
+    // Offers every entry of other, as when combining the halls of several searches
+    pub fn merge(&mut self, other: HallOfFame<T>) {
+        for (score, key, item) in other.entries {
+            if self.admits(score) {
+                self.insert(score, key, || item);
+            }
+        }
+    }
+
+    // Same scores and keys with the items converted, to merge halls of different item types
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> HallOfFame<U> {
+        HallOfFame {
+            capacity: self.capacity,
+            entries: self.entries.iter().map(|(s, k, item)| (*s, k.clone(), f(item))).collect(),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (f64, &T)> {
+        self.entries.iter().map(|(s, _, item)| (*s, item))
+    }
+}
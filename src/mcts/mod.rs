@@ -1,51 +1,292 @@
-use indextree::{Arena, NodeId};
-use rand::prelude::{SliceRandom, ThreadRng};
+use indextree::{Arena, NodeEdge, NodeId};
+use rand::prelude::ThreadRng;
 use rand::Rng;
 use std::any::type_name;
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use crate::game::{Game, GameArena};
+use crate::mcts::hall_of_fame::HallOfFame;
+use crate::mcts::prior::{PriorPolicy, UniformPrior};
+use crate::mcts::rave::RaveTable;
+use crate::mcts::rollout::{RolloutPolicy, UniformRollout};
+use crate::mcts::stats::{DepthDistribution, SearchStats};
 
-#[derive(Debug, Default)]
-pub struct MctsNode {
-    action: u64,
+pub mod export;
+pub mod hall_of_fame;
+pub mod prior;
+pub mod rave;
+pub mod rollout;
+pub mod stats;
+
+// Unvisited children with AMAF statistics are still tried first, but in order of their AMAF value
+const UNVISITED_PRIORITY: f64 = 1e9;
+
+#[derive(Debug)]
+pub struct MctsNode<A> {
+    // None only for the root
+    action: Option<A>,
     best_rollout: f64,
     num_simulations: f64,
     total_accumulation: f64,
+    // Sum of squared rollout values, for variance based heuristics
+    sum_of_squares: f64,
+    // Normalized over the siblings, see PriorPolicy
+    prior: f64,
 
     // Heuristic to solve the multi-armed bandit problem
     is_expanded: bool,
     terminally_searched: bool,
 }
 
-pub struct MctsArena<G: Game, H: Heuristic> {
-    arena: Arena<MctsNode>,
+impl<A> MctsNode<A> {
+    fn new(action: Option<A>, prior: f64) -> Self {
+        Self {
+            action,
+            best_rollout: 0.0,
+            num_simulations: 0.0,
+            total_accumulation: 0.0,
+            sum_of_squares: 0.0,
+            prior,
+            is_expanded: false,
+            terminally_searched: false,
+        }
+    }
+
+    // None for the root
+    pub fn action(&self) -> Option<&A> {
+        self.action.as_ref()
+    }
+
+    pub fn best_rollout(&self) -> f64 {
+        self.best_rollout
+    }
+
+    pub fn num_simulations(&self) -> f64 {
+        self.num_simulations
+    }
+
+    pub fn total_accumulation(&self) -> f64 {
+        self.total_accumulation
+    }
+
+    pub fn sum_of_squares(&self) -> f64 {
+        self.sum_of_squares
+    }
+
+    pub fn prior(&self) -> f64 {
+        self.prior
+    }
+
+    pub fn is_expanded(&self) -> bool {
+        self.is_expanded
+    }
+
+    // Population variance of the rollouts below the node
+    pub fn variance(&self) -> f64 {
+        if self.num_simulations == 0.0 {
+            return 0.0;
+        }
+        let mean = self.total_accumulation / self.num_simulations;
+        (self.sum_of_squares / self.num_simulations - mean * mean).max(0.0)
+    }
+
+    fn record(&mut self, best: f64, sum: f64, sum_of_squares: f64, rollouts: f64) {
+        self.best_rollout = f64::max(best, self.best_rollout);
+        self.total_accumulation += sum;
+        self.sum_of_squares += sum_of_squares;
+        self.num_simulations += rollouts;
+    }
+}
+
+pub struct MctsArena<
+    G: Game,
+    H: Heuristic,
+    P: PriorPolicy<G> = UniformPrior,
+    R: RolloutPolicy<G> = UniformRollout,
+> {
+    arena: Arena<MctsNode<G::Action>>,
     root: NodeId,
     num_rollouts: usize,
+    // Bounds on the rollouts per expansion when adapting them to the variance
+    adaptive_rollouts: Option<(usize, usize)>,
+    rounds_per_iteration: usize,
     best_game: G,
     best_score: f64,
+    // Distinct top rollouts up to Game::score_key, empty unless enabled
+    hall_of_fame: HallOfFame<G>,
     game: G,
     rng: ThreadRng,
     heuristic: H,
+    prior: P,
+    rollout_policy: R,
+    // Reused between calls to Game::get_actions and PriorPolicy::priors
+    actions: Vec<G::Action>,
+    priors: Vec<f64>,
+    rave: Option<RaveTable<G::Action>>,
+    // Actions of the current rollout, only recorded for RAVE
+    trace: Vec<G::Action>,
+    // Scores of terminal games by Game::score_key, shared by all rollouts
+    score_memo: Option<HashMap<Vec<usize>, f64>>,
+    stats: SearchStats,
     _p: PhantomData<G>,
 }
 
 impl<G: Game, H: Heuristic> MctsArena<G, H> {
     pub fn new(game: G, heuristic: H) -> Self {
+        Self::with_prior(game, heuristic, UniformPrior)
+    }
+}
+
+impl<G: Game, H: Heuristic, P: PriorPolicy<G>> MctsArena<G, H, P> {
+    pub fn with_prior(game: G, heuristic: H, prior: P) -> Self {
+        Self::with_policies(game, heuristic, prior, UniformRollout)
+    }
+}
+
+impl<G: Game, H: Heuristic, P: PriorPolicy<G>, R: RolloutPolicy<G>> MctsArena<G, H, P, R> {
+    pub fn with_policies(game: G, heuristic: H, prior: P, rollout_policy: R) -> Self {
         let mut arena = Arena::new();
-        let root = arena.new_node(MctsNode::default());
+        let root = arena.new_node(MctsNode::new(None, 1.0));
         Self {
             arena,
             root,
             num_rollouts: 50,
+            adaptive_rollouts: None,
+            rounds_per_iteration: 79,
             best_game: game.start(),
             best_score: f64::NEG_INFINITY,
+            hall_of_fame: HallOfFame::new(0),
             game,
             rng: rand::thread_rng(),
             heuristic,
+            prior,
+            rollout_policy,
+            actions: vec![],
+            priors: vec![],
+            rave: None,
+            trace: vec![],
+            score_memo: None,
+            stats: SearchStats::new(),
             _p: PhantomData,
         }
     }
 
+    pub fn set_num_rollouts(&mut self, num_rollouts: usize) {
+        assert!(num_rollouts > 0, "MctsArena::set_num_rollouts needs at least one rollout");
+        self.num_rollouts = num_rollouts;
+    }
+
+    pub fn set_rounds_per_iteration(&mut self, rounds: usize) {
+        assert!(rounds > 0, "MctsArena::set_rounds_per_iteration needs at least one round");
+        self.rounds_per_iteration = rounds;
+    }
+
+    /*
+     * Scales the rollouts per expansion by the rollout variance of the expanded node relative to
+     * that of the whole search, so that uncertain nodes get more samples
+     */
+    pub fn set_adaptive_rollouts(&mut self, min: usize, max: usize) {
+        assert!(
+            0 < min && min <= max,
+            "MctsArena::set_adaptive_rollouts bounds must satisfy 0 < min <= max"
+        );
+        self.adaptive_rollouts = Some((min, max));
+    }
+
+    fn rollouts_for(&self, node_id: NodeId) -> usize {
+        let Some((min, max)) = self.adaptive_rollouts else {
+            return self.num_rollouts;
+        };
+        let node = self.arena[node_id].get();
+        let ratio = node.variance() / self.arena[self.root].get().variance();
+        if node.num_simulations == 0.0 || !ratio.is_finite() {
+            return self.num_rollouts.clamp(min, max);
+        }
+        ((self.num_rollouts as f64 * ratio).round() as usize).clamp(min, max)
+    }
+
+    /*
+     * Plays rounds_per_iteration rounds and prunes, true if the whole tree has been searched
+     */
+    pub fn run_iteration(&mut self) -> bool {
+        for _i in 0..self.rounds_per_iteration {
+            self.play_round();
+        }
+        self.prune()
+    }
+
+    /*
+     * Blends all-moves-as-first statistics into selection, see RaveTable
+     */
+    pub fn enable_rave(&mut self, equivalence: f64) {
+        self.rave = Some(RaveTable::new(equivalence));
+    }
+
+    /*
+     * Keeps the best k terminal games distinct up to Game::score_key, so that ties and
+     * non-isomorphic co-optimal graphs are reported along with the best game
+     */
+    pub fn set_hall_of_fame(&mut self, k: usize) {
+        self.hall_of_fame = HallOfFame::new(k);
+    }
+
+    pub fn hall_of_fame(&self) -> &HallOfFame<G> {
+        &self.hall_of_fame
+    }
+
+    /*
+     * Memoizes the scores of terminal games by Game::score_key, so that graphs isomorphic to one
+     * already scored are not scored again. Worthwhile when scoring costs more than the key.
+     */
+    pub fn enable_score_memo(&mut self) {
+        self.score_memo = Some(HashMap::new());
+    }
+
+    /*
+     * Scores the terminal games of one expansion together by Game::score_batch, after looking
+     * them up in the memo if enabled
+     */
+    fn score_all(&mut self, games: &[G]) -> Vec<f64> {
+        let Some(memo) = &mut self.score_memo else {
+            return G::score_batch(&games.iter().collect::<Vec<_>>());
+        };
+        let mut scores = vec![0.0; games.len()];
+        let mut pending = vec![];
+        for (k, game) in games.iter().enumerate() {
+            let key = game.score_key();
+            match key.as_ref().and_then(|key| memo.get(key)) {
+                Some(&score) => {
+                    scores[k] = score;
+                    self.stats.record_memo_hit();
+                }
+                None => pending.push((k, key)),
+            }
+        }
+        let batch: Vec<&G> = pending.iter().map(|&(k, _)| &games[k]).collect();
+        for ((k, key), score) in pending.into_iter().zip(G::score_batch(&batch)) {
+            scores[k] = score;
+            if let Some(key) = key {
+                memo.insert(key, score);
+            }
+        }
+        scores
+    }
+
+    fn selection_value(&self, id: NodeId, parent: NodeId) -> f64 {
+        let node = self.arena[id].get();
+        let h = self.heuristic.heuristic(node, self.arena[parent].get());
+        let Some(amaf) = self.rave.as_ref().zip(node.action).and_then(|(r, a)| r.get(&a)) else {
+            return h;
+        };
+        let amaf_value = self.heuristic.value(amaf);
+        if h.is_infinite() {
+            UNVISITED_PRIORITY + amaf_value
+        } else {
+            let beta = self.rave.as_ref().unwrap().beta(node.num_simulations);
+            h + beta * (amaf_value - self.heuristic.value(node))
+        }
+    }
+
     fn select(&mut self) -> (NodeId, G) {
         let mut node_id = self.root;
         let mut game = self.game.start();
@@ -57,7 +298,7 @@ impl<G: Game, H: Heuristic> MctsArena<G, H> {
             let mut next = node_id;
             // println!("Selecting");
             for id in node_id.children(&self.arena) {
-                let h = self.heuristic.heuristic(self.arena[id].get(), self.arena[node_id].get());
+                let h = self.selection_value(id, node_id);
                 if h > best {
                     best = h;
                     next = id;
@@ -70,7 +311,7 @@ impl<G: Game, H: Heuristic> MctsArena<G, H> {
                 break;
             } else {
                 node_id = next;
-                game.act(self.arena.get(node_id).unwrap().get().action);
+                game.act(self.arena.get(node_id).unwrap().get().action.unwrap());
             }
         }
         let node = self.arena.get_mut(node_id).unwrap().get_mut();
@@ -90,40 +331,57 @@ impl<G: Game, H: Heuristic> MctsArena<G, H> {
         if game.is_terminal() {
             return (parent, game);
         }
-        let actions = game.get_actions();
+        game.get_actions(&mut self.actions);
+        self.prior.priors(&game, &self.actions, &mut self.priors);
+        let mut total: f64 = self.priors.iter().sum();
+        if total <= 0.0 {
+            self.priors.fill(1.0);
+            total = self.priors.len() as f64;
+        }
+
+        // The child rolled out first is sampled according to the priors
+        let mut chosen_index = self.actions.len() - 1;
+        let mut r = self.rng.gen::<f64>() * total;
+        for (index, p) in self.priors.iter().enumerate() {
+            if r < *p {
+                chosen_index = index;
+                break;
+            }
+            r -= p;
+        }
+
         let mut selected = parent;
-        let chosen_index = self.rng.gen_range(0..actions.len());
-        for (index, act) in actions.iter().enumerate() {
-            let id = parent.append_value(
-                MctsNode {
-                    action: *act,
-                    ..Default::default()
-                },
-                &mut self.arena,
-            );
+        for (index, act) in self.actions.iter().enumerate() {
+            let node = MctsNode::new(Some(*act), self.priors[index] / total);
+            let id = parent.append_value(node, &mut self.arena);
             if index == chosen_index {
                 selected = id;
             }
         }
-        game.act(actions[chosen_index]);
-        return (selected, game);
+        game.act(self.actions[chosen_index]);
+        (selected, game)
     }
 
-    fn rollout(&mut self, game: &mut G) -> f64 {
+    fn rollout(&mut self, game: &mut G) {
         while !game.is_terminal() {
-            let action = *game.get_actions().choose(&mut rand::thread_rng()).unwrap();
+            game.get_actions(&mut self.actions);
+            let action = self.rollout_policy.choose(game, &self.actions, &mut self.rng);
+            if self.rave.is_some() {
+                self.trace.push(action);
+            }
             game.act(action);
         }
-        game.get_score()
     }
 
-    fn backpropagate(&mut self, selected: NodeId, best: f64, sum: f64) {
+    fn backpropagate(&mut self, selected: NodeId, best: f64, sum: f64, sum_of_squares: f64, rollouts: usize) {
+        let rollouts = rollouts as f64;
         let mut node_id = selected;
         loop {
             let node = self.arena.get_mut(node_id).unwrap().get_mut();
-            node.best_rollout = f64::max(best, node.best_rollout);
-            node.total_accumulation += sum;
-            node.num_simulations += self.num_rollouts as f64;
+            node.record(best, sum, sum_of_squares, rollouts);
+            if let (Some(rave), Some(action)) = (&mut self.rave, node.action) {
+                rave.record_path(action, best, sum, sum_of_squares, rollouts);
+            }
 
             if let Some(next) = self.arena[node_id].parent() {
                 node_id = next;
@@ -131,6 +389,9 @@ impl<G: Game, H: Heuristic> MctsArena<G, H> {
                 break;
             }
         }
+        if let Some(rave) = &mut self.rave {
+            rave.commit();
+        }
         /*node_id = selected;
         while let Some(next) = self.arena[node_id].parent() {
             let nh = self.heuristic.heuristic(
@@ -151,6 +412,7 @@ impl<G: Game, H: Heuristic> MctsArena<G, H> {
             for id in node_id.children(&self.arena).collect::<Vec<_>>() {
                 if self.terminate_leaves(id) {
                     id.remove(&mut self.arena);
+                    self.stats.record_pruned();
                 } else {
                     terminated = false;
                 }
@@ -169,6 +431,39 @@ impl<G: Game, H: Heuristic> MctsArena<G, H> {
         self.arena.count()
     }
 
+    pub fn stats(&self) -> &SearchStats {
+        &self.stats
+    }
+
+    pub fn depth_distribution(&self) -> DepthDistribution {
+        let mut distribution = DepthDistribution::default();
+        let mut depth = 0;
+        for edge in self.root.traverse(&self.arena) {
+            match edge {
+                NodeEdge::Start(_) => {
+                    distribution.record(depth);
+                    depth += 1;
+                }
+                NodeEdge::End(_) => depth -= 1,
+            }
+        }
+        distribution
+    }
+
+    /*
+     * The top depth levels of the search tree in Graphviz DOT, see mcts::export
+     */
+    pub fn to_dot(&self, depth: usize) -> String {
+        export::to_dot(&self.arena, self.root, depth)
+    }
+
+    /*
+     * The top depth levels of the search tree as nested JSON objects, see mcts::export
+     */
+    pub fn to_json(&self, depth: usize) -> String {
+        export::to_json(&self.arena, self.root, depth)
+    }
+
     #[allow(dead_code)]
     pub(crate) fn print(&self) {
         let p = self.root.debug_pretty_print(&self.arena);
@@ -176,44 +471,65 @@ impl<G: Game, H: Heuristic> MctsArena<G, H> {
     }
 
     #[allow(dead_code)]
-    pub(crate) fn select_route(&mut self) -> Vec<u64> {
+    pub(crate) fn select_route(&mut self) -> Vec<G::Action> {
         let mut v = vec![];
         let (n, g) = self.select();
         println!("{}", g.is_terminal());
         println!("{}", g.get_score());
         println!("{}", self.arena[n].get().best_rollout);
         for a in n.ancestors(&self.arena) {
-            v.push(self.arena[a].get().action);
+            v.extend(self.arena[a].get().action);
         }
         v.reverse();
         v
     }
 
-    pub(crate) fn best_game(&self) -> G {
+    pub fn best_game(&self) -> G {
         self.best_game.clone()
     }
 }
 
-impl<G: Game, H: Heuristic> GameArena<G> for MctsArena<G, H> {
+impl<G: Game, H: Heuristic, P: PriorPolicy<G>, R: RolloutPolicy<G>> GameArena<G> for MctsArena<G, H, P, R> {
     fn play_round(&mut self) {
         let p = self.select();
+        let num_rollouts = self.rollouts_for(p.0);
         let (parent, game) = self.expand(p);
         let mut best = 0.0;
+        let mut worst = f64::INFINITY;
         let mut sum = 0.0;
-        for _i in 1..=self.num_rollouts {
+        let mut sum_of_squares = 0.0;
+        let mut finished = Vec::with_capacity(num_rollouts);
+        let mut traces = vec![];
+        for _i in 1..=num_rollouts {
             let mut g = game.clone();
-            let val = self.rollout(&mut g);
+            self.trace.clear();
+            self.rollout(&mut g);
+            if self.rave.is_some() {
+                traces.push(self.trace.clone());
+            }
+            finished.push(g);
+        }
+        let scores = self.score_all(&finished);
+        for (k, (g, val)) in finished.into_iter().zip(scores).enumerate() {
+            if let Some(rave) = &mut self.rave {
+                rave.record_rollout(&traces[k], val);
+            }
             if val > best {
                 best = val;
             }
+            worst = worst.min(val);
+            self.hall_of_fame.offer(val, || g.score_key(), || g.clone());
             if val > self.best_score {
-                self.best_game = g.clone();
+                self.best_game = g;
                 self.best_score = val;
+                self.stats.record_best(val);
             }
             sum += val;
+            sum_of_squares += val * val;
         }
-        self.heuristic.update_heuristic(sum, best, self.num_rollouts);
-        self.backpropagate(parent, best, sum);
+        self.heuristic.update_heuristic(sum, best, worst, num_rollouts);
+        self.backpropagate(parent, best, sum, sum_of_squares, num_rollouts);
+        self.stats.record_round(num_rollouts);
     }
 
     fn best(&self) -> f64 {
@@ -222,36 +538,94 @@ impl<G: Game, H: Heuristic> GameArena<G> for MctsArena<G, H> {
 }
 
 pub trait Heuristic {
-    fn heuristic(&self, node: &MctsNode, parent: &MctsNode) -> f64;
-    fn update_heuristic(&mut self, rollout_sum: f64, best: f64, new_rollouts: usize);
+    fn heuristic<A>(&self, node: &MctsNode<A>, parent: &MctsNode<A>) -> f64;
+    // The normalized exploitation term of the heuristic, zero for unvisited nodes
+    fn value<A>(&self, node: &MctsNode<A>) -> f64;
+    fn update_heuristic(&mut self, rollout_sum: f64, best: f64, worst: f64, new_rollouts: usize);
 }
 
+// What UCT considers the value of a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exploitation {
+    // Best rollout seen below the node, suits a search for a single maximizer
+    Best,
+    // Mean of the rollouts below the node, the textbook UCT
+    Mean,
+}
+
+// How UCT scales node values into [0, 1]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Normalization {
+    // Divide by the upper score bound, which must then be reasonably tight
+    Bound,
+    // Rescale by the rollouts observed so far, no bound required
+    Observed,
+}
+
+#[allow(clippy::upper_case_acronyms)]
 pub struct UCT {
     pub(crate) exploration: f64,
+    exploitation: Exploitation,
+    normalization: Normalization,
     num_rollouts: f64,
     mean_rollout: f64,
     best_rollout: f64,
+    worst_rollout: f64,
     upper_bound: f64,
 }
 
 impl UCT {
-    pub(crate) fn new(exploration: f64) -> Self {
+    pub fn new(exploration: f64) -> Self {
         Self {
             exploration,
+            exploitation: Exploitation::Best,
+            normalization: Normalization::Bound,
             num_rollouts: 0.0,
             mean_rollout: 0.0,
-            best_rollout: 1.0,
+            best_rollout: f64::NEG_INFINITY,
+            worst_rollout: f64::INFINITY,
             upper_bound: 1.0,
         }
     }
 
-    pub(crate) fn set_upper_score_bound(&mut self, bound: f64) {
+    // In the same space as the scores, ln of the bound for TreeGame::set_log_score
+    pub fn set_upper_score_bound(&mut self, bound: f64) {
         self.upper_bound = bound;
     }
+
+    pub fn set_exploitation(&mut self, exploitation: Exploitation) {
+        self.exploitation = exploitation;
+    }
+
+    pub fn set_normalization(&mut self, normalization: Normalization) {
+        self.normalization = normalization;
+    }
+
+    /*
+     * With observed normalization the best rollout maps to 1. Best rollouts of nodes cluster near
+     * the top of the observed range, so they are measured from the mean rollout rather than the
+     * worst one, anything below the mean maps to 0.
+     */
+    fn normalize(&self, value: f64) -> f64 {
+        match self.normalization {
+            Normalization::Bound => value / self.upper_bound,
+            Normalization::Observed => {
+                let low = match self.exploitation {
+                    Exploitation::Best => self.mean_rollout,
+                    Exploitation::Mean => self.worst_rollout,
+                };
+                if self.best_rollout > low {
+                    ((value - low) / (self.best_rollout - low)).clamp(0.0, 1.0)
+                } else {
+                    1.0
+                }
+            }
+        }
+    }
 }
 
 impl Heuristic for UCT {
-    fn heuristic(&self, node: &MctsNode, parent: &MctsNode) -> f64 {
+    fn heuristic<A>(&self, node: &MctsNode<A>, parent: &MctsNode<A>) -> f64 {
         if node.num_simulations == 0.0 {
             // Also tried return 1.0
             // Returning infinity is more sensible and seems to give better results
@@ -259,18 +633,146 @@ impl Heuristic for UCT {
             // return 1.0;
         }
 
-        /*node.total_accumulation / (self.upper_bound * node.num_simulations)
-            + self.exploration * (parent.num_simulations.ln() / node.num_simulations).sqrt()*/
-        node.best_rollout / self.upper_bound
+        self.value(node)
             + self.exploration * (parent.num_simulations.ln() / node.num_simulations).sqrt()
-        /*node.best_rollout / self.best_rollout
-            + self.exploration * (parent.num_simulations.ln() / node.num_simulations).sqrt()*/
     }
 
-    fn update_heuristic(&mut self, rollout_sum: f64, best: f64, new_rollouts: usize) {
+    fn value<A>(&self, node: &MctsNode<A>) -> f64 {
+        if node.num_simulations == 0.0 {
+            return 0.0;
+        }
+        self.normalize(match self.exploitation {
+            Exploitation::Best => node.best_rollout,
+            Exploitation::Mean => node.total_accumulation / node.num_simulations,
+        })
+    }
+
+    fn update_heuristic(&mut self, rollout_sum: f64, best: f64, worst: f64, new_rollouts: usize) {
         self.mean_rollout *= self.num_rollouts / (self.num_rollouts + new_rollouts as f64);
         self.num_rollouts += new_rollouts as f64;
         self.mean_rollout += rollout_sum / self.num_rollouts;
         self.best_rollout = self.best_rollout.max(best);
+        self.worst_rollout = self.worst_rollout.min(worst);
+    }
+}
+
+/*
+ * PUCT as in AlphaZero, exploration is weighted by the prior of each child so that unvisited
+ * children are no longer tried in arbitrary order
+ */
+pub struct Puct {
+    pub(crate) exploration: f64,
+    upper_bound: f64,
+}
+
+impl Puct {
+    pub fn new(exploration: f64) -> Self {
+        Self {
+            exploration,
+            upper_bound: 1.0,
+        }
+    }
+
+    pub fn set_upper_score_bound(&mut self, bound: f64) {
+        self.upper_bound = bound;
+    }
+}
+
+impl Heuristic for Puct {
+    fn heuristic<A>(&self, node: &MctsNode<A>, parent: &MctsNode<A>) -> f64 {
+        self.value(node)
+            + self.exploration * node.prior * parent.num_simulations.sqrt() / (1.0 + node.num_simulations)
+    }
+
+    fn value<A>(&self, node: &MctsNode<A>) -> f64 {
+        if node.num_simulations == 0.0 {
+            return 0.0;
+        }
+        node.best_rollout / self.upper_bound
+    }
+
+    fn update_heuristic(&mut self, _rollout_sum: f64, _best: f64, _worst: f64, _new_rollouts: usize) {}
+}
+
+/*
+ * Single-player MCTS (Schadd et al. 2008), UCT on the mean plus the term
+ * sqrt((sum x^2 - n mean^2 + D) / n) which rewards nodes with a high variance of rollouts
+ */
+pub struct SpMcts {
+    pub(crate) exploration: f64,
+    // The constant D, added to the variance of rarely visited nodes
+    deviation_bonus: f64,
+    upper_bound: f64,
+}
+
+impl SpMcts {
+    pub fn new(exploration: f64, deviation_bonus: f64) -> Self {
+        Self {
+            exploration,
+            deviation_bonus,
+            upper_bound: 1.0,
+        }
+    }
+
+    pub fn set_upper_score_bound(&mut self, bound: f64) {
+        self.upper_bound = bound;
+    }
+}
+
+impl Heuristic for SpMcts {
+    fn heuristic<A>(&self, node: &MctsNode<A>, parent: &MctsNode<A>) -> f64 {
+        if node.num_simulations == 0.0 {
+            return f64::INFINITY;
+        }
+
+        let n = node.num_simulations;
+        let mean = self.value(node);
+        let squares = node.sum_of_squares / (self.upper_bound * self.upper_bound);
+        let deviation = ((squares - n * mean * mean).max(0.0) + self.deviation_bonus) / n;
+        mean + self.exploration * (parent.num_simulations.ln() / n).sqrt() + deviation.sqrt()
     }
-}
\ No newline at end of file
+
+    fn value<A>(&self, node: &MctsNode<A>) -> f64 {
+        if node.num_simulations == 0.0 {
+            return 0.0;
+        }
+        node.total_accumulation / (self.upper_bound * node.num_simulations)
+    }
+
+    fn update_heuristic(&mut self, _rollout_sum: f64, _best: f64, _worst: f64, _new_rollouts: usize) {}
+}
+
+/*
+ * Runtime choice between the heuristics above
+ */
+pub enum AnyHeuristic {
+    Uct(UCT),
+    SpMcts(SpMcts),
+    Puct(Puct),
+}
+
+impl Heuristic for AnyHeuristic {
+    fn heuristic<A>(&self, node: &MctsNode<A>, parent: &MctsNode<A>) -> f64 {
+        match self {
+            AnyHeuristic::Uct(h) => h.heuristic(node, parent),
+            AnyHeuristic::SpMcts(h) => h.heuristic(node, parent),
+            AnyHeuristic::Puct(h) => h.heuristic(node, parent),
+        }
+    }
+
+    fn value<A>(&self, node: &MctsNode<A>) -> f64 {
+        match self {
+            AnyHeuristic::Uct(h) => h.value(node),
+            AnyHeuristic::SpMcts(h) => h.value(node),
+            AnyHeuristic::Puct(h) => h.value(node),
+        }
+    }
+
+    fn update_heuristic(&mut self, rollout_sum: f64, best: f64, worst: f64, new_rollouts: usize) {
+        match self {
+            AnyHeuristic::Uct(h) => h.update_heuristic(rollout_sum, best, worst, new_rollouts),
+            AnyHeuristic::SpMcts(h) => h.update_heuristic(rollout_sum, best, worst, new_rollouts),
+            AnyHeuristic::Puct(h) => h.update_heuristic(rollout_sum, best, worst, new_rollouts),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::game::{Game, GraphGame};
+use crate::graph::Graph;
+use crate::tree_game::TreeGame;
+
+/*
+ * Move priors attached to children by MctsArena::expand, used by PUCT style heuristics.
+ * Priors need not be normalized, the arena divides by their sum.
+ */
+pub trait PriorPolicy<G: Game> {
+    // Clears `priors` and fills it with one weight per action
+    fn priors(&self, game: &G, actions: &[G::Action], priors: &mut Vec<f64>);
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UniformPrior;
+
+impl<G: Game> PriorPolicy<G> for UniformPrior {
+    fn priors(&self, _game: &G, actions: &[G::Action], priors: &mut Vec<f64>) {
+        priors.clear();
+        priors.resize(actions.len(), 1.0);
+    }
+}
+
+/*
+ * Prefers adding edges between low degree vertices in TreeGame.
+ * Including the current edge is favoured while both endpoints stay at or below the average degree
+ * 2m/n of the finished graph, with `strength` controlling how sharply. Zero strength is uniform.
+ */
+#[derive(Debug, Clone, Copy)]
+pub struct DegreeBalancePrior {
+    pub strength: f64,
+}
+
+impl DegreeBalancePrior {
+    pub fn new(strength: f64) -> Self {
+        Self { strength }
+    }
+}
+
+impl<G: Graph> PriorPolicy<TreeGame<G>> for DegreeBalancePrior {
+    fn priors(&self, game: &TreeGame<G>, actions: &[<TreeGame<G> as Game>::Action], priors: &mut Vec<f64>) {
+        priors.clear();
+        let (i, j) = game.current_edge();
+        let average = 2.0 * game.max_edges() as f64 / game.num_vertices() as f64;
+        let degree = game.graph().degree(i).max(game.graph().degree(j)) + 1;
+        let include = 1.0 / (1.0 + (self.strength * (degree as f64 - average)).exp());
+        // On multigraphs the inclusion prior is shared evenly by the edge counts
+        let counts = actions.iter().filter(|a| a.count > 0).count().max(1) as f64;
+        priors.extend(actions.iter().map(|a| if a.count > 0 { include / counts } else { 1.0 - include }));
+    }
+}
@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use crate::mcts::MctsNode;
+
+/*
+ * All-moves-as-first statistics shared by every node and keyed by action.
+ * An action is credited with each rollout it took part in wherever it was played, so decisions
+ * which recur under many parents, such as the edges of TreeGame, learn from the whole search.
+ */
+pub struct RaveTable<A> {
+    // Visit count at which a node's own statistics weigh as much as the AMAF ones
+    equivalence: f64,
+    stats: HashMap<A, MctsNode<A>>,
+    // Statistics of the current round, merged by MctsArena::backpropagate
+    pending: HashMap<A, MctsNode<A>>,
+}
+
+impl<A: Copy + Eq + Hash> RaveTable<A> {
+    pub fn new(equivalence: f64) -> Self {
+        Self {
+            equivalence,
+            stats: HashMap::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn get(&self, action: &A) -> Option<&MctsNode<A>> {
+        self.stats.get(action)
+    }
+
+    pub fn len(&self) -> usize {
+        self.stats.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stats.is_empty()
+    }
+
+    /*
+     * Weight of the AMAF value for a node visited n times, the schedule of Gelly and Silver
+     */
+    pub fn beta(&self, n: f64) -> f64 {
+        (self.equivalence / (3.0 * n + self.equivalence)).sqrt()
+    }
+
+    pub(crate) fn record_rollout(&mut self, actions: &[A], value: f64) {
+        for action in actions {
+            self.pending
+                .entry(*action)
+                .or_insert_with(|| MctsNode::new(None, 1.0))
+                .record(value, value, value * value, 1.0);
+        }
+    }
+
+    // Actions on the tree path took part in every rollout of the round
+    pub(crate) fn record_path(&mut self, action: A, best: f64, sum: f64, sum_of_squares: f64, rollouts: f64) {
+        self.pending
+            .entry(action)
+            .or_insert_with(|| MctsNode::new(None, 1.0))
+            .record(best, sum, sum_of_squares, rollouts);
+    }
+
+    pub(crate) fn commit(&mut self) {
+        for (action, round) in self.pending.drain() {
+            self.stats
+                .entry(action)
+                .or_insert_with(|| MctsNode::new(None, 1.0))
+                .record(round.best_rollout, round.total_accumulation, round.sum_of_squares, round.num_simulations);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use rand::prelude::SliceRandom;
+use rand::rngs::ThreadRng;
+use rand::Rng;
+use crate::game::{Game, GraphGame};
+use crate::graph::Graph;
+use crate::tree_game::{EdgeDecision, TreeGame};
+
+/*
+ * Chooses the moves of MctsArena::rollout, `actions` is never empty
+ */
+pub trait RolloutPolicy<G: Game> {
+    fn choose(&mut self, game: &G, actions: &[G::Action], rng: &mut ThreadRng) -> G::Action;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UniformRollout;
+
+impl<G: Game> RolloutPolicy<G> for UniformRollout {
+    fn choose(&mut self, _game: &G, actions: &[G::Action], rng: &mut ThreadRng) -> G::Action {
+        *actions.choose(rng).unwrap()
+    }
+}
+
+// The action adding no copy or a single copy of the edge, or the only one available
+fn decide(actions: &[EdgeDecision], include: bool) -> EdgeDecision {
+    *actions
+        .iter()
+        .find(|a| a.count == include as usize)
+        .unwrap_or(&actions[0])
+}
+
+/*
+ * Adds the current edge of TreeGame with probability remaining edges / remaining pairs,
+ * spreading the added edges evenly over the edge order instead of clustering them at its start
+ */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EdgeBudgetRollout;
+
+impl<G: Graph> RolloutPolicy<TreeGame<G>> for EdgeBudgetRollout {
+    fn choose(&mut self, game: &TreeGame<G>, actions: &[EdgeDecision], rng: &mut ThreadRng) -> EdgeDecision {
+        let p = game.remaining_edges() as f64 / game.remaining_pairs() as f64;
+        decide(actions, rng.gen::<f64>() < p)
+    }
+}
+
+/*
+ * Greedily adds the current edge of TreeGame while both endpoints stay within the target degree
+ * ceil(2m/n), and whenever the remaining pairs are needed to place all edges.
+ * With probability `epsilon` a uniformly random action is taken instead, so rollouts differ.
+ * The target assumes the vertex count is close to that of a maximizer.
+ */
+#[derive(Debug, Clone, Copy)]
+pub struct DegreeBalanceRollout {
+    pub epsilon: f64,
+}
+
+impl DegreeBalanceRollout {
+    pub fn new(epsilon: f64) -> Self {
+        Self { epsilon }
+    }
+}
+
+impl<G: Graph> RolloutPolicy<TreeGame<G>> for DegreeBalanceRollout {
+    fn choose(&mut self, game: &TreeGame<G>, actions: &[EdgeDecision], rng: &mut ThreadRng) -> EdgeDecision {
+        if rng.gen::<f64>() < self.epsilon {
+            return *actions.choose(rng).unwrap();
+        }
+        let (i, j) = game.current_edge();
+        let target = (2 * game.max_edges()).div_ceil(game.num_vertices());
+        let degree = game.graph().degree(i).max(game.graph().degree(j)) + 1;
+        decide(actions, degree <= target || game.remaining_edges() >= game.remaining_pairs())
+    }
+}
@@ -0,0 +1,109 @@
+use std::time::{Duration, Instant};
+
+/*
+ * Counters collected by MctsArena over the course of a search, see MctsArena::stats.
+ * The clock starts when the arena is created.
+ */
+#[derive(Debug, Clone)]
+pub struct SearchStats {
+    start: Instant,
+    rounds: usize,
+    rollouts: usize,
+    nodes_pruned: usize,
+    memo_hits: usize,
+    // Elapsed time and score at every improvement of the best score
+    best_history: Vec<(Duration, f64)>,
+}
+
+impl SearchStats {
+    pub(crate) fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            rounds: 0,
+            rollouts: 0,
+            nodes_pruned: 0,
+            memo_hits: 0,
+            best_history: vec![],
+        }
+    }
+
+    pub(crate) fn record_round(&mut self, rollouts: usize) {
+        self.rounds += 1;
+        self.rollouts += rollouts;
+    }
+
+    pub(crate) fn record_best(&mut self, score: f64) {
+        self.best_history.push((self.start.elapsed(), score));
+    }
+
+    pub(crate) fn record_pruned(&mut self) {
+        self.nodes_pruned += 1;
+    }
+
+    pub(crate) fn record_memo_hit(&mut self) {
+        self.memo_hits += 1;
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    pub fn rollouts(&self) -> usize {
+        self.rollouts
+    }
+
+    pub fn rollouts_per_second(&self) -> f64 {
+        self.rollouts as f64 / self.elapsed().as_secs_f64()
+    }
+
+    pub fn nodes_pruned(&self) -> usize {
+        self.nodes_pruned
+    }
+
+    // Rollouts scored from MctsArena's memo, see MctsArena::enable_score_memo
+    pub fn memo_hits(&self) -> usize {
+        self.memo_hits
+    }
+
+    // Time at which the current best score was first found
+    pub fn time_to_best(&self) -> Option<Duration> {
+        self.best_history.last().map(|&(t, _)| t)
+    }
+
+    pub fn best_history(&self) -> &[(Duration, f64)] {
+        &self.best_history
+    }
+}
+
+// Number of nodes at each depth of the search tree, the root being at depth 0
+#[derive(Debug, Clone, Default)]
+pub struct DepthDistribution {
+    counts: Vec<usize>,
+}
+
+impl DepthDistribution {
+    pub(crate) fn record(&mut self, depth: usize) {
+        if self.counts.len() <= depth {
+            self.counts.resize(depth + 1, 0);
+        }
+        self.counts[depth] += 1;
+    }
+
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.counts.len().saturating_sub(1)
+    }
+
+    pub fn mean_depth(&self) -> f64 {
+        let nodes: usize = self.counts.iter().sum();
+        let total: usize = self.counts.iter().enumerate().map(|(d, c)| d * c).sum();
+        total as f64 / nodes.max(1) as f64
+    }
+}
@@ -0,0 +1,117 @@
+/*
+ * Python bindings, enabled with the `python` feature.
+ *
+ * >>> import tree_hunter
+ * >>> search = tree_hunter.TreeSearch(9, 81)
+ * >>> search.run(100)
+ * >>> networkx.Graph(search.best_adjacency())
+ */
+
+// pyo3 0.22 macros expand to conversions newer clippy considers useless
+#![allow(clippy::useless_conversion)]
+
+use std::collections::{BTreeMap, HashSet};
+use pyo3::exceptions::{PyOverflowError, PyValueError};
+use pyo3::prelude::*;
+use crate::game::{GameArena, GraphGame};
+use crate::graph::laplacian::LapGraph;
+use crate::graph::Graph;
+use crate::mcts::{MctsArena, UCT};
+use crate::tree_game::{default_vertex_count, TreeGame};
+
+// MctsArena holds a thread local rng, so the search is pinned to the thread that created it
+#[pyclass(unsendable)]
+pub struct TreeSearch {
+    arena: MctsArena<TreeGame<LapGraph>, UCT>,
+}
+
+#[pymethods]
+impl TreeSearch {
+    #[new]
+    #[pyo3(signature = (edge_count, upper_bound, vertex_count = None, exploration = 3.0))]
+    fn new(edge_count: usize, upper_bound: f64, vertex_count: Option<usize>, exploration: f64) -> PyResult<Self> {
+        let n = vertex_count.unwrap_or_else(|| default_vertex_count(edge_count));
+        if n < 2 {
+            return Err(PyValueError::new_err("vertex_count must be at least 2"));
+        }
+        let mut h = UCT::new(exploration);
+        h.set_upper_score_bound(upper_bound);
+        Ok(Self {
+            arena: MctsArena::new(TreeGame::new(edge_count, n), h),
+        })
+    }
+
+    /// Runs `search_iterations` batches of rounds, pruning exhausted subtrees after each batch.
+    /// Returns True if the whole search space was exhausted.
+    #[pyo3(signature = (search_iterations, rounds = 79))]
+    fn run(&mut self, search_iterations: usize, rounds: usize) -> PyResult<bool> {
+        if rounds == 0 {
+            return Err(PyValueError::new_err("rounds must be at least 1"));
+        }
+        self.arena.set_rounds_per_iteration(rounds);
+        for _j in 0..search_iterations {
+            if self.arena.run_iteration() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn play_round(&mut self) {
+        self.arena.play_round();
+    }
+
+    #[getter]
+    fn best_score(&self) -> f64 {
+        self.arena.best()
+    }
+
+    #[getter]
+    fn tree_size(&self) -> usize {
+        self.arena.tree_size()
+    }
+
+    /// Edge list of the best graph found, accepted by networkx.Graph
+    fn best_edges(&self) -> Vec<(usize, usize)> {
+        self.arena.best_game().graph().edges()
+    }
+
+    /// Dict of lists adjacency of the best graph found, accepted by networkx.Graph
+    fn best_adjacency(&self) -> BTreeMap<usize, Vec<usize>> {
+        let mut adjacency = BTreeMap::<usize, Vec<usize>>::new();
+        for (i, j) in self.arena.best_game().graph().edges() {
+            adjacency.entry(i).or_default().push(j);
+            adjacency.entry(j).or_default().push(i);
+        }
+        adjacency
+    }
+}
+
+/// Number of spanning trees of the simple graph with the given edges. Isolated vertices are ignored.
+#[pyfunction]
+fn spanning_tree_count(edges: Vec<(usize, usize)>) -> PyResult<usize> {
+    let n = edges.iter().map(|&(i, j)| i.max(j) + 1).max().unwrap_or(0);
+    let mut g = LapGraph::empty(n);
+    let mut seen = HashSet::new();
+    for (i, j) in edges {
+        if i == j {
+            return Err(PyValueError::new_err(format!("self loop at vertex {i}")));
+        }
+        if !seen.insert((i.max(j), i.min(j))) {
+            return Err(PyValueError::new_err(format!("repeated edge ({i}, {j})")));
+        }
+        g.add_edge(i, j);
+    }
+    let count = g.weighted_spanning_tree_count().round();
+    if count >= usize::MAX as f64 {
+        return Err(PyOverflowError::new_err(format!("{count:e} spanning trees overflow usize")));
+    }
+    Ok(count as usize)
+}
+
+#[pymodule]
+fn tree_hunter(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<TreeSearch>()?;
+    m.add_function(wrap_pyfunction!(spanning_tree_count, m)?)?;
+    Ok(())
+}
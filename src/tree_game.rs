@@ -1,15 +1,36 @@
-use crate::game::Game;
+use std::cell::Cell;
+use crate::game::{Game, GraphGame};
 use crate::graph::Graph;
 
+/*
+ * How many copies of the edge currently under consideration are added to the graph, 0 or 1 unless
+ * TreeGame::set_max_multiplicity allows multigraphs.
+ * The edge is carried along so that stale decisions are rejected by TreeGame::act.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EdgeDecision {
+    pub edge: (usize, usize),
+    pub count: usize,
+}
+
+// Vertices searched over when none are given, small edge counts need more than m for all their edges
+pub fn default_vertex_count(m: usize) -> usize {
+    m.max(3)
+}
+
 #[derive(Clone, Debug)]
 pub struct TreeGame<G: Graph> {
     max_edges: usize,
     num_vertices: usize,
+    max_multiplicity: usize,
     num_actions: usize,
     max_actions: usize,
     num_edges_added: usize,
     graph: G,
     current_edge: (usize, usize),
+    log_score: bool,
+    // Score of the current graph, cleared by act
+    score: Cell<Option<f64>>,
 }
 
 impl<G: Graph> TreeGame<G> {
@@ -17,70 +38,132 @@ impl<G: Graph> TreeGame<G> {
         Self {
             max_edges: m,
             num_vertices: n,
+            max_multiplicity: 1,
             num_actions: 0,
             max_actions: n * (n - 1) / 2,
             num_edges_added: 0,
             graph: G::empty(n),
             // .0 > .1
             current_edge: (1, 0),
+            log_score: false,
+            score: Cell::new(None),
         }
     }
 
+    /*
+     * Scores by ln of the spanning tree count, which stays meaningful where the count overflows.
+     * Graphs without spanning trees score 0 like trees, so that scores are never infinite.
+     */
+    pub fn set_log_score(&mut self, log_score: bool) {
+        self.log_score = log_score;
+        self.score.set(None);
+    }
+
+    /*
+     * Searches multigraphs in which each pair is joined by up to max_multiplicity edges.
+     * Pairs are decided in the same order and with the same symmetry breaking on unused vertices.
+     */
+    pub fn set_max_multiplicity(&mut self, max_multiplicity: usize) {
+        assert!(max_multiplicity > 0, "TreeGame::set_max_multiplicity needs at least one edge per pair");
+        self.max_multiplicity = max_multiplicity;
+    }
+
+    pub fn max_multiplicity(&self) -> usize {
+        self.max_multiplicity
+    }
+
     pub fn print_graph(&self) {
         self.graph.print_edges();
     }
-}
 
-impl<G: Graph> Game for TreeGame<G> {
-    fn get_actions(&self) -> Vec<u64> {
-        if self.num_edges_added >= self.max_edges || self.num_actions >= self.max_actions {
-            return vec![];
-        }
+    // The pair (i, j), i > j, decided by the next action
+    pub fn current_edge(&self) -> (usize, usize) {
+        self.current_edge
+    }
+
+    pub fn max_edges(&self) -> usize {
+        self.max_edges
+    }
+
+    pub fn num_vertices(&self) -> usize {
+        self.num_vertices
+    }
+
+    pub fn remaining_edges(&self) -> usize {
+        self.max_edges - self.num_edges_added
+    }
+
+    // Pairs still to be decided, including the current one
+    pub fn remaining_pairs(&self) -> usize {
+        self.max_actions - self.num_actions
+    }
+
+    /*
+     * Whether the current edge may be added without breaking the symmetry of unused vertices
+     */
+    fn can_include(&self) -> bool {
         if self.num_edges_added == 0 {
-            return vec![1];
+            return true;
         }
 
         let d1 = self.graph.degree(self.current_edge.0);
         let d2 = self.graph.degree(self.current_edge.1);
         if d1 == 0 && d2 == 0 {
-            return vec![0, 1];
+            return true;
         }
         if d1 == 0 {
-            if self.current_edge.0 == self.graph.lowest_free_vertex().unwrap() {
-                vec![0, 1]
-            }else {
-                vec![0]
-            }
-        }else if d2 == 0 {
-            if self.current_edge.1 == self.graph.lowest_free_vertex().unwrap() {
-                vec![0, 1]
-            }else {
-                vec![0]
-            }
-        }else {
-            vec![0, 1]
+            self.current_edge.0 == self.graph.lowest_free_vertex().unwrap()
+        } else if d2 == 0 {
+            self.current_edge.1 == self.graph.lowest_free_vertex().unwrap()
+        } else {
+            true
+        }
+    }
+}
+
+impl<G: Graph> Game for TreeGame<G> {
+    type Action = EdgeDecision;
+
+    fn get_actions(&self, actions: &mut Vec<EdgeDecision>) {
+        actions.clear();
+        if self.is_terminal() {
+            return;
+        }
+        let edge = self.current_edge;
+        if self.num_edges_added > 0 {
+            actions.push(EdgeDecision { edge, count: 0 });
+        }
+        if self.can_include() {
+            let most = self.max_multiplicity.min(self.remaining_edges());
+            actions.extend((1..=most).map(|count| EdgeDecision { edge, count }));
         }
-        // vec![0, 1]
     }
 
     fn is_terminal(&self) -> bool {
         self.num_edges_added >= self.max_edges || self.num_actions >= self.max_actions
     }
 
-    fn act(&mut self, action: u64) -> bool {
-        if action != 0 && action != 1 {
+    fn act(&mut self, action: EdgeDecision) -> bool {
+        if action.edge != self.current_edge {
             return false;
         }
         if self.num_edges_added >= self.max_edges {
             return false;
         }
+        if action.count > self.max_multiplicity || action.count > self.remaining_edges() {
+            return false;
+        }
         if self.num_actions >= self.max_actions {
             return false;
         }
-        if action == 1 {
-            self.num_edges_added += 1;
+        if action.count > 0 {
             self.graph
                 .add_edge(self.current_edge.0, self.current_edge.1);
+            for _k in 1..action.count {
+                self.graph
+                    .add_parallel_edge(self.current_edge.0, self.current_edge.1);
+            }
+            self.num_edges_added += action.count;
         }
 
         self.current_edge.0 += 1;
@@ -90,23 +173,63 @@ impl<G: Graph> Game for TreeGame<G> {
         }
 
         self.num_actions += 1;
+        self.score.set(None);
 
-        return true;
+        true
     }
 
     fn get_score(&self) -> f64 {
-        self.graph.spanning_tree_count() as f64
+        if let Some(score) = self.score.get() {
+            return score;
+        }
+        let score = if self.log_score {
+            self.graph.ln_spanning_tree_count().max(0.0)
+        } else {
+            // Rounded in floating point, since the count overflows usize long before f64
+            self.graph.weighted_spanning_tree_count().round()
+        };
+        self.score.set(Some(score));
+        score
+    }
+
+    // Counts the graphs together, see Graph::batch_spanning_tree_counts
+    fn score_batch(games: &[&Self]) -> Vec<f64> {
+        let pending: Vec<&Self> = games
+            .iter()
+            .copied()
+            .filter(|g| g.score.get().is_none() && !g.log_score)
+            .collect();
+        let graphs: Vec<&G> = pending.iter().map(|g| &g.graph).collect();
+        for (g, count) in pending.iter().zip(G::batch_spanning_tree_counts(&graphs)) {
+            g.score.set(Some(count.round()));
+        }
+        games.iter().map(|g| g.get_score()).collect()
     }
 
     fn start(&self) -> Self {
         Self {
             max_edges: self.max_edges,
             num_vertices: self.num_vertices,
+            max_multiplicity: self.max_multiplicity,
             num_edges_added: 0,
             num_actions: 0,
             max_actions: self.max_actions,
             graph: G::empty(self.num_vertices),
             current_edge: (1, 0),
+            log_score: self.log_score,
+            score: Cell::new(None),
         }
     }
+
+    fn score_key(&self) -> Option<Vec<usize>> {
+        Some(self.graph.canonical_form())
+    }
+}
+
+impl<G: Graph> GraphGame for TreeGame<G> {
+    type Graph = G;
+
+    fn graph(&self) -> &G {
+        &self.graph
+    }
 }
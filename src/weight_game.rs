@@ -0,0 +1,140 @@
+use std::rc::Rc;
+use crate::game::{Game, GraphGame};
+use crate::graph::Graph;
+
+/*
+ * Units of weight given to the candidate edge currently under consideration.
+ * The edge is carried along so that stale decisions are rejected by WeightGame::act.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WeightAllocation {
+    pub edge: (usize, usize),
+    pub units: usize,
+}
+
+/*
+ * Distributes a total weight budget over a fixed set of candidate edges so as to maximize the
+ * weighted spanning tree count, as in network reliability design.
+ * The budget is split into equal units and the candidates are decided in order, each receiving
+ * any number of the remaining units. The last candidate receives whatever is left, since unspent
+ * weight never increases the score.
+ */
+#[derive(Clone, Debug)]
+pub struct WeightGame<G: Graph> {
+    num_vertices: usize,
+    // Shared between the clones made for rollouts
+    candidates: Rc<[(usize, usize)]>,
+    budget: f64,
+    units: usize,
+    num_units_spent: usize,
+    num_actions: usize,
+    graph: G,
+}
+
+impl<G: Graph> WeightGame<G> {
+    pub fn new(n: usize, candidates: Vec<(usize, usize)>, budget: f64, units: usize) -> Self {
+        assert!(budget > 0.0 && budget.is_finite(), "WeightGame::new needs a positive budget");
+        assert!(units > 0, "WeightGame::new needs at least one unit");
+        Self {
+            num_vertices: n,
+            candidates: candidates.into(),
+            budget,
+            units,
+            num_units_spent: 0,
+            num_actions: 0,
+            graph: G::empty(n),
+        }
+    }
+
+    // Every pair of the n vertices is a candidate
+    pub fn complete(n: usize, budget: f64, units: usize) -> Self {
+        let candidates = (1..n).flat_map(|i| (0..i).map(move |j| (i, j))).collect();
+        Self::new(n, candidates, budget, units)
+    }
+
+    pub fn unit_weight(&self) -> f64 {
+        self.budget / self.units as f64
+    }
+
+    pub fn remaining_units(&self) -> usize {
+        self.units - self.num_units_spent
+    }
+
+    // The candidate edge decided by the next action
+    pub fn current_edge(&self) -> Option<(usize, usize)> {
+        self.candidates.get(self.num_actions).copied()
+    }
+}
+
+impl<G: Graph> Game for WeightGame<G> {
+    type Action = WeightAllocation;
+
+    fn get_actions(&self, actions: &mut Vec<WeightAllocation>) {
+        actions.clear();
+        if self.is_terminal() {
+            return;
+        }
+        let edge = self.candidates[self.num_actions];
+        let remaining = self.remaining_units();
+        if self.num_actions + 1 == self.candidates.len() {
+            actions.push(WeightAllocation { edge, units: remaining });
+        } else {
+            actions.extend((0..=remaining).map(|units| WeightAllocation { edge, units }));
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.num_units_spent >= self.units || self.num_actions >= self.candidates.len()
+    }
+
+    fn act(&mut self, action: WeightAllocation) -> bool {
+        if self.is_terminal() || action.edge != self.candidates[self.num_actions] {
+            return false;
+        }
+        if action.units > self.remaining_units() {
+            return false;
+        }
+        if action.units > 0 {
+            let weight = action.units as f64 * self.unit_weight();
+            self.graph.add_weighted_edge(action.edge.0, action.edge.1, weight);
+        }
+        self.num_units_spent += action.units;
+        self.num_actions += 1;
+        true
+    }
+
+    fn get_score(&self) -> f64 {
+        self.graph.weighted_spanning_tree_count()
+    }
+
+    fn start(&self) -> Self {
+        Self {
+            num_vertices: self.num_vertices,
+            candidates: self.candidates.clone(),
+            budget: self.budget,
+            units: self.units,
+            num_units_spent: 0,
+            num_actions: 0,
+            graph: G::empty(self.num_vertices),
+        }
+    }
+
+    // Weightings are isomorphic when their multigraphs with one edge per unit are
+    fn score_key(&self) -> Option<Vec<usize>> {
+        let mut units = G::empty(self.num_vertices);
+        for (i, j) in self.graph.edges() {
+            for _k in 0..(self.graph.weight(i, j) / self.unit_weight()).round() as usize {
+                units.add_parallel_edge(i, j);
+            }
+        }
+        Some(units.canonical_form())
+    }
+}
+
+impl<G: Graph> GraphGame for WeightGame<G> {
+    type Graph = G;
+
+    fn graph(&self) -> &G {
+        &self.graph
+    }
+}
@@ -0,0 +1,66 @@
+use tree_hunter::graph::analysis::analyze;
+use tree_hunter::{Graph, LapGraph};
+
+fn petersen() -> LapGraph {
+    let mut g = LapGraph::empty(10);
+    for i in 0..5 {
+        g.add_edge((i + 1) % 5, i);
+        g.add_edge(i + 5, i);
+        g.add_edge(5 + (i + 2) % 5, i + 5);
+    }
+    g
+}
+
+#[test]
+fn petersen_graph() {
+    let a = analyze(&petersen());
+    assert_eq!(a.regularity, Some(3));
+    assert_eq!(a.diameter, Some(2));
+    assert_eq!(a.girth, Some(5));
+    assert_eq!(a.vertex_connectivity, 3);
+    assert_eq!(a.edge_connectivity, 3);
+    assert_eq!(a.automorphisms, 120);
+    assert!((a.algebraic_connectivity - 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn complete_graphs() {
+    let mut factorial = 1;
+    for n in 2..=6 {
+        factorial *= n as u128;
+        let a = analyze(&LapGraph::complete(n));
+        assert_eq!(a.regularity, Some(n - 1));
+        assert_eq!(a.diameter, Some(1));
+        assert_eq!(a.girth, if n >= 3 { Some(3) } else { None });
+        assert_eq!(a.vertex_connectivity, n - 1);
+        assert_eq!(a.edge_connectivity, n - 1);
+        assert_eq!(a.automorphisms, factorial, "automorphisms of K_{n}");
+    }
+}
+
+#[test]
+fn empty_graph() {
+    let a = analyze(&LapGraph::empty(4));
+    assert!(a.degree_sequence.is_empty());
+    assert_eq!(a.diameter, None);
+    assert_eq!(a.girth, None);
+    assert_eq!(a.vertex_connectivity, 0);
+    assert_eq!(a.edge_connectivity, 0);
+    assert!(a.laplacian_spectrum.is_empty());
+    assert_eq!(a.algebraic_connectivity, 0.0);
+}
+
+// A double edge on a path of three vertices
+#[test]
+fn multigraph() {
+    let mut g = LapGraph::empty(3);
+    g.add_parallel_edge(1, 0);
+    g.add_parallel_edge(1, 0);
+    g.add_edge(2, 1);
+    let a = analyze(&g);
+    assert_eq!(a.degree_sequence, [3, 2, 1]);
+    assert_eq!(a.girth, Some(2));
+    assert_eq!(a.edge_connectivity, 1);
+    assert_eq!(a.vertex_connectivity, 1);
+    assert_eq!(a.automorphisms, 1);
+}
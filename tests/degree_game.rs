@@ -0,0 +1,60 @@
+use tree_hunter::{DegreeAction, DegreeGame, Game, Graph, GraphGame, LapGraph};
+
+// Realizes the chosen degree sequence by always taking the first edge offered
+fn realize(mut game: DegreeGame<LapGraph>) -> LapGraph {
+    let mut actions = vec![];
+    while !game.is_terminal() {
+        game.get_actions(&mut actions);
+        assert!(!actions.is_empty(), "chosen degree sequences must be realizable");
+        assert!(game.act(actions[0]));
+    }
+    game.graph().clone()
+}
+
+// Visits every degree sequence the game offers, checking each realization
+fn check_sequences(game: &DegreeGame<LapGraph>, m: usize, spread: usize) -> usize {
+    let mut actions = vec![];
+    game.get_actions(&mut actions);
+    if !matches!(actions.first(), Some(DegreeAction::Degree(_))) {
+        let g = realize(game.clone());
+        let degrees: Vec<usize> = (0..m.max(3)).map(|v| g.degree(v)).filter(|&d| d > 0).collect();
+        let (low, high) = (degrees.iter().min().unwrap(), degrees.iter().max().unwrap());
+        assert!(high - low <= spread, "degrees {degrees:?} exceed spread {spread}");
+        assert_eq!(g.size(), m);
+        return 1;
+    }
+    actions
+        .iter()
+        .map(|&action| {
+            let mut next = game.clone();
+            assert!(next.act(action));
+            check_sequences(&next, m, spread)
+        })
+        .sum()
+}
+
+#[test]
+fn degree_sequences_stay_within_spread() {
+    for m in 1..=12 {
+        for spread in 0..=2 {
+            let game = DegreeGame::<LapGraph>::new(m, m.max(3), spread);
+            let mut actions = vec![];
+            game.get_actions(&mut actions);
+            if !actions.is_empty() {
+                assert!(check_sequences(&game, m, spread) > 0);
+            }
+        }
+    }
+}
+
+// After a degree of 3 with spread 1, no later degree may be below 2
+#[test]
+fn spread_applies_to_every_degree() {
+    let mut game = DegreeGame::<LapGraph>::new(8, 8, 1);
+    assert!(game.act(DegreeAction::Degree(3)));
+    let mut actions = vec![];
+    game.get_actions(&mut actions);
+    assert!(!actions.is_empty());
+    assert!(actions.iter().all(|a| matches!(a, DegreeAction::Degree(k) if (2..=3).contains(k))));
+    assert!(!game.act(DegreeAction::Degree(1)));
+}
@@ -0,0 +1,48 @@
+use tree_hunter::exhaustive::exhaustive;
+use tree_hunter::known::known_result;
+use tree_hunter::{Graph, LapGraph};
+
+// Connected graphs with 1, 2, ... edges up to isomorphism, OEIS A002905
+const CLASS_COUNTS: [usize; 12] = [1, 1, 3, 5, 12, 30, 79, 227, 710, 2322, 8071, 29503];
+
+#[test]
+fn enumerates_every_isomorphism_class() {
+    // The last two take too long for debug builds
+    for (m, &count) in (1..).zip(&CLASS_COUNTS[..10]) {
+        let result = exhaustive::<LapGraph>(m, m + 1);
+        assert_eq!(result.edge_count, m);
+        assert_eq!(result.graphs, count, "classes with {m} edges");
+    }
+}
+
+#[test]
+fn maximum_matches_known_results() {
+    for m in 1..=9 {
+        let result = exhaustive::<LapGraph>(m, m + 1);
+        let known = known_result(m).unwrap();
+        assert_eq!(result.spanning_trees, known.spanning_trees, "maximum with {m} edges");
+        assert!(!result.maximizers.is_empty());
+        for g in &result.maximizers {
+            assert_eq!(g.size(), m);
+            assert_eq!(g.spanning_tree_count(), known.spanning_trees);
+        }
+    }
+}
+
+// The lone vertex is one class, without spanning trees as for Graph
+#[test]
+fn no_edges_leave_the_single_vertex() {
+    let result = exhaustive::<LapGraph>(0, 1);
+    assert_eq!(result.graphs, 1);
+    assert_eq!(result.spanning_trees, 0);
+    assert_eq!(result.maximizers.len(), 1);
+    assert_eq!(result.maximizers[0].spanning_tree_count(), result.spanning_trees);
+}
+
+// Five edges need at least four vertices
+#[test]
+fn too_few_vertices_enumerate_nothing() {
+    let result = exhaustive::<LapGraph>(5, 3);
+    assert_eq!(result.graphs, 0);
+    assert!(result.maximizers.is_empty());
+}
@@ -0,0 +1,61 @@
+use tree_hunter::mcts::hall_of_fame::HallOfFame;
+
+fn items(hall: &HallOfFame<&'static str>) -> Vec<(f64, &'static str)> {
+    hall.iter().map(|(s, &item)| (s, item)).collect()
+}
+
+#[test]
+fn full_hall_admits_only_strictly_better_scores() {
+    let mut hall = HallOfFame::new(2);
+    assert!(hall.offer(3.0, || None, || "a"));
+    assert!(hall.offer(1.0, || None, || "b"));
+    assert!(!hall.offer(1.0, || panic!("key of a rejected score"), || "c"));
+    assert!(!hall.offer(0.5, || None, || "d"));
+    assert!(hall.offer(2.0, || None, || "e"));
+    assert_eq!(items(&hall), [(3.0, "a"), (2.0, "e")]);
+}
+
+#[test]
+fn equal_keys_are_kept_once() {
+    let mut hall = HallOfFame::new(3);
+    assert!(hall.offer(2.0, || Some(vec![1]), || "a"));
+    assert!(!hall.offer(5.0, || Some(vec![1]), || "b"));
+    // Items without a key are always distinct
+    assert!(hall.offer(1.0, || None, || "c"));
+    assert!(hall.offer(1.0, || None, || "d"));
+    assert_eq!(items(&hall), [(2.0, "a"), (1.0, "c"), (1.0, "d")]);
+}
+
+#[test]
+fn ties_keep_their_order_of_arrival() {
+    let mut hall = HallOfFame::new(3);
+    hall.offer(1.0, || Some(vec![1]), || "a");
+    hall.offer(1.0, || Some(vec![2]), || "b");
+    hall.offer(2.0, || Some(vec![3]), || "c");
+    hall.offer(1.0, || Some(vec![4]), || "d");
+    assert_eq!(items(&hall), [(2.0, "c"), (1.0, "a"), (1.0, "b")]);
+}
+
+#[test]
+fn merge_stays_within_capacity() {
+    let mut hall = HallOfFame::new(3);
+    hall.offer(4.0, || Some(vec![1]), || "a");
+    hall.offer(2.0, || Some(vec![2]), || "b");
+    let mut other = HallOfFame::new(3);
+    other.offer(4.0, || Some(vec![1]), || "a'");
+    other.offer(3.0, || Some(vec![3]), || "c");
+    other.offer(1.0, || Some(vec![4]), || "d");
+    hall.merge(other);
+    assert_eq!(hall.len(), hall.capacity());
+    assert_eq!(items(&hall), [(4.0, "a"), (3.0, "c"), (2.0, "b")]);
+}
+
+#[test]
+fn zero_capacity_keeps_nothing() {
+    let mut hall = HallOfFame::new(0);
+    assert!(!hall.offer(1.0, || panic!("key of a rejected score"), || "a"));
+    let mut other = HallOfFame::new(2);
+    other.offer(1.0, || None, || "b");
+    hall.merge(other);
+    assert!(hall.is_empty());
+}
@@ -0,0 +1,75 @@
+use tree_hunter::{EdgeDecision, Game, Graph, GraphGame, LapGraph, SparseGraph, TreeGame};
+
+fn counts(game: &TreeGame<LapGraph>) -> Vec<usize> {
+    let mut actions = vec![];
+    game.get_actions(&mut actions);
+    actions.iter().map(|a| a.count).collect()
+}
+
+fn decide(game: &mut TreeGame<LapGraph>, count: usize) {
+    let edge = game.current_edge();
+    assert!(game.act(EdgeDecision { edge, count }));
+}
+
+#[test]
+fn counts_are_bounded_by_multiplicity_and_remaining_edges() {
+    // The first edge is never skipped
+    let mut game = TreeGame::<LapGraph>::new(5, 4);
+    assert_eq!(counts(&game), [1]);
+    game.set_max_multiplicity(3);
+    assert_eq!(counts(&game), [1, 2, 3]);
+    decide(&mut game, 3);
+    assert_eq!(counts(&game), [0, 1, 2]);
+    let edge = game.current_edge();
+    assert!(!game.act(EdgeDecision { edge, count: 3 }));
+    decide(&mut game, 1);
+    assert_eq!(counts(&game), [0, 1]);
+    decide(&mut game, 1);
+    assert!(game.is_terminal());
+    assert_eq!(game.graph().size(), 5);
+}
+
+// A triple edge with a pendant edge has one spanning tree per copy
+#[test]
+fn multigraph_spanning_trees() {
+    let mut game = TreeGame::<LapGraph>::new(4, 3);
+    game.set_max_multiplicity(3);
+    decide(&mut game, 3);
+    decide(&mut game, 1);
+    assert!(game.is_terminal());
+    assert_eq!(game.get_score(), 3.0);
+    assert_eq!(game.graph().multiplicity(1, 0), 3);
+
+    let mut g = SparseGraph::empty(3);
+    for _k in 0..3 {
+        g.add_parallel_edge(1, 0);
+    }
+    g.add_edge(2, 0);
+    assert_eq!(g.spanning_tree_count(), 3);
+}
+
+fn path_with_double_edge<G: Graph>(doubled: (usize, usize)) -> G {
+    let mut g = G::empty(4);
+    for (i, j) in [(1, 0), (2, 1), (3, 2)] {
+        g.add_edge(i, j);
+    }
+    g.add_parallel_edge(doubled.0, doubled.1);
+    g
+}
+
+// A path of three edges, doubling an end edge or the middle one
+fn canonical_forms_tell_multiplicities_apart<G: Graph>() {
+    let end = path_with_double_edge::<G>((1, 0));
+    let other_end = path_with_double_edge::<G>((3, 2));
+    let middle = path_with_double_edge::<G>((2, 1));
+    assert_eq!(end.canonical_form(), other_end.canonical_form());
+    assert_ne!(end.canonical_form(), middle.canonical_form());
+    assert_eq!(end.spanning_tree_count(), 2);
+    assert_eq!(middle.spanning_tree_count(), 2);
+}
+
+#[test]
+fn canonical_forms() {
+    canonical_forms_tell_multiplicities_apart::<LapGraph>();
+    canonical_forms_tell_multiplicities_apart::<SparseGraph>();
+}
@@ -0,0 +1,132 @@
+use tree_hunter::{EdgeDecision, Game, Graph, LapGraph, SparseGraph, TreeGame};
+
+fn from_edges<G: Graph>(n: usize, edges: &[(usize, usize)]) -> G {
+    let mut g = G::empty(n);
+    for &(i, j) in edges {
+        g.add_edge(i, j);
+    }
+    g
+}
+
+fn cycle(n: usize, offset: usize) -> Vec<(usize, usize)> {
+    (0..n).map(|k| (offset + (k + 1) % n, offset + k)).collect()
+}
+
+fn check_no_spanning_trees<G: Graph>(g: &G) {
+    assert!(!g.is_connected());
+    assert_eq!(g.spanning_tree_count(), 0);
+    assert_eq!(g.weighted_spanning_tree_count(), 0.0);
+    assert_eq!(g.ln_spanning_tree_count(), f64::NEG_INFINITY);
+}
+
+fn empty_graphs<G: Graph>() {
+    check_no_spanning_trees(&G::empty(0));
+    check_no_spanning_trees(&G::empty(5));
+}
+
+// A lone vertex is isolated, so it counts as no graph at all
+fn single_vertex<G: Graph>() {
+    check_no_spanning_trees(&G::empty(1));
+}
+
+fn disconnected<G: Graph>() {
+    check_no_spanning_trees(&from_edges::<G>(4, &[(1, 0), (3, 2)]));
+    let mut edges = cycle(3, 0);
+    edges.extend(cycle(4, 5));
+    check_no_spanning_trees(&from_edges::<G>(10, &edges));
+}
+
+fn isolated_vertices_are_ignored<G: Graph>() {
+    let g = from_edges::<G>(8, &[(3, 1), (5, 3), (5, 1)]);
+    assert!(g.is_connected());
+    assert_eq!(g.order(), 3);
+    assert_eq!(g.spanning_tree_count(), 3);
+    assert_eq!(from_edges::<G>(2, &[(1, 0)]).spanning_tree_count(), 1);
+}
+
+fn complete_graphs<G: Graph>() {
+    for n in 2..9usize {
+        let expected = n.pow(n as u32 - 2);
+        assert_eq!(G::complete(n).spanning_tree_count(), expected);
+        let ln = G::complete(n).ln_spanning_tree_count();
+        assert!((ln - (expected as f64).ln()).abs() < 1e-9);
+    }
+}
+
+// Two long cycles joined by one edge have a tiny algebraic connectivity, but 50 * 50 spanning trees
+fn nearly_disconnected<G: Graph>() {
+    let mut edges = cycle(50, 0);
+    edges.extend(cycle(50, 50));
+    edges.push((50, 0));
+    assert_eq!(from_edges::<G>(100, &edges).spanning_tree_count(), 2500);
+}
+
+fn weighted<G: Graph>() {
+    // Triangle with weights a, b, c has ab + bc + ca weighted spanning trees
+    let mut g = G::empty(3);
+    g.add_weighted_edge(1, 0, 2.0);
+    g.add_weighted_edge(2, 0, 3.0);
+    g.add_weighted_edge(2, 1, 0.5);
+    assert!((g.weighted_spanning_tree_count() - 8.5).abs() < 1e-9);
+    assert!((g.ln_spanning_tree_count() - 8.5f64.ln()).abs() < 1e-9);
+}
+
+// Graphs agreeing on the edges at vertices 0 to 3, with duplicates and a disconnected graph
+fn batch<G: Graph>() {
+    let prefix = [(1, 0), (2, 0), (3, 0), (2, 1), (4, 1), (4, 3), (5, 2)];
+    let suffixes: [&[(usize, usize)]; 5] = [&[(5, 4)], &[(5, 4), (6, 5)], &[(5, 4)], &[(6, 5), (6, 4)], &[]];
+    let graphs: Vec<G> = suffixes
+        .iter()
+        .map(|suffix| {
+            let mut edges = prefix.to_vec();
+            edges.extend_from_slice(suffix);
+            from_edges(7, &edges)
+        })
+        .collect();
+    let mut graphs: Vec<&G> = graphs.iter().collect();
+    let disconnected = from_edges::<G>(7, &[(1, 0), (3, 2)]);
+    graphs.push(&disconnected);
+    let counts = G::batch_spanning_tree_counts(&graphs);
+    assert_eq!(counts.len(), graphs.len());
+    for (g, count) in graphs.iter().zip(counts) {
+        assert!((count - g.weighted_spanning_tree_count()).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn lap_graph() {
+    empty_graphs::<LapGraph>();
+    single_vertex::<LapGraph>();
+    disconnected::<LapGraph>();
+    isolated_vertices_are_ignored::<LapGraph>();
+    complete_graphs::<LapGraph>();
+    nearly_disconnected::<LapGraph>();
+    weighted::<LapGraph>();
+    batch::<LapGraph>();
+}
+
+#[test]
+fn sparse_graph() {
+    empty_graphs::<SparseGraph>();
+    single_vertex::<SparseGraph>();
+    disconnected::<SparseGraph>();
+    isolated_vertices_are_ignored::<SparseGraph>();
+    complete_graphs::<SparseGraph>();
+    nearly_disconnected::<SparseGraph>();
+    weighted::<SparseGraph>();
+    batch::<SparseGraph>();
+}
+
+// K_60 has 60^58 spanning trees, far beyond usize, which scores must not saturate at
+#[test]
+fn scores_exceed_usize() {
+    let n = 60;
+    let mut game = TreeGame::<LapGraph>::new(n * (n - 1) / 2, n);
+    while !game.is_terminal() {
+        let edge = game.current_edge();
+        assert!(game.act(EdgeDecision { edge, count: 1 }));
+    }
+    let expected = (n as f64).powi(n as i32 - 2);
+    assert!(expected > usize::MAX as f64);
+    assert!((game.get_score() / expected - 1.0).abs() < 1e-6);
+}